@@ -33,7 +33,10 @@ use transmog::Format;
 
 pub use self::{
     reader::TransmogReader,
-    writer::{AsyncDestination, SyncDestination, TransmogWriter, TransmogWriterFor},
+    writer::{
+        AsyncDestination, NewlineDelimitedDestination, SyncDestination, TransmogWriter,
+        TransmogWriterFor,
+    },
 };
 
 /// Builder helper to specify types without the need of turbofishing.
@@ -114,6 +117,17 @@ where
     pub fn for_sync(self) -> TransmogStream<TReads, TWrites, TStream, SyncDestination, TFormat> {
         TransmogStream::new(self.stream, self.format)
     }
+
+    /// Build this stream to terminate each serialized value with a newline,
+    /// and to expect the same of received values.
+    ///
+    /// This is necessary for compatability with newline-delimited protocols,
+    /// such as [JSON Lines](https://jsonlines.org/).
+    pub fn for_newline_delimited(
+        self,
+    ) -> TransmogStream<TReads, TWrites, TStream, NewlineDelimitedDestination, TFormat> {
+        TransmogStream::new(self.stream, self.format).for_newline_delimited()
+    }
 }
 
 /// A wrapper around an asynchronous stream that receives and sends bincode-encoded values.
@@ -230,6 +244,26 @@ where
         let (stream, format) = self.into_inner();
         TransmogStream::new(stream, format)
     }
+
+    /// Make this stream terminate each serialized value with a newline, and
+    /// expect the same of received values.
+    ///
+    /// This is necessary for compatability with newline-delimited protocols,
+    /// such as [JSON Lines](https://jsonlines.org/).
+    pub fn for_newline_delimited(
+        self,
+    ) -> TransmogStream<TReads, TWrites, TStream, NewlineDelimitedDestination, TFormat> {
+        let (stream, format) = self.into_inner();
+        TransmogStream {
+            stream: TransmogReader::new(
+                InternalTransmogWriter(
+                    TransmogWriter::new(stream, format.clone()).for_newline_delimited(),
+                ),
+                format,
+            )
+            .newline_delimited(),
+        }
+    }
 }
 
 /// A reader of Transmog-encoded data from a [`TcpStream`](tokio::net::TcpStream).
@@ -259,6 +293,7 @@ where
     ) {
         // First, steal the reader state so it isn't lost
         let rbuff = self.stream.buffer.split();
+        let framing = self.stream.framing;
         // Then, fish out the writer
         let writer = &mut self.stream.get_mut().0;
         let format = writer.format().clone();
@@ -270,6 +305,7 @@ where
         // Then put the reader back together
         let mut reader = TransmogReader::new(r, format.clone());
         reader.buffer = rbuff;
+        reader.framing = framing;
         // And then the writer
         let mut writer: TransmogWriter<_, _, TDestination, TFormat> =
             TransmogWriter::new(w, format).make_for();
@@ -358,6 +394,7 @@ mod tests {
     use futures::prelude::*;
     use transmog::OwnedDeserializer;
     use transmog_bincode::Bincode;
+    use transmog_bitcode::Bitcode;
     use transmog_pot::Pot;
 
     use super::*;
@@ -407,6 +444,51 @@ mod tests {
         it_works(Pot::default(), &[vec![0_u8; 1_000_000]]).await;
     }
 
+    #[tokio::test]
+    async fn it_works_bitcode() {
+        // Test short payloads
+        it_works(Bitcode::default(), &[44, 42]).await;
+        it_works(Bitcode::default().serde(), &[44, 42]).await;
+        // Test a long payload
+        it_works(Bitcode::default(), &[vec![0_u8; 1_000_000]]).await;
+    }
+
+    #[tokio::test]
+    async fn newline_delimited_empty_values() {
+        let mut writer =
+            TransmogWriter::new(Vec::new(), Bincode::default()).for_newline_delimited();
+        writer.send(()).await.unwrap();
+        writer.send(()).await.unwrap();
+        let (written, _) = writer.into_inner();
+        assert_eq!(written, b"\n\n");
+
+        let reader =
+            TransmogReader::<_, (), _>::new(&written[..], Bincode::default()).newline_delimited();
+        let read = reader.map(Result::unwrap).collect::<Vec<_>>().await;
+        assert_eq!(read, [(), ()]);
+    }
+
+    #[tokio::test]
+    async fn newline_delimited_rejects_newlines() {
+        // Bincode serializes 10_u8 as a single 0x0A byte, which would be read
+        // back as an empty value followed by the rest of the stream.
+        let mut writer =
+            TransmogWriter::new(Vec::new(), Bincode::default()).for_newline_delimited();
+        writer.send(9_u8).await.unwrap();
+        let err = writer.send(10_u8).await.unwrap_err();
+        assert!(
+            matches!(&*err, transmog_bincode::bincode::ErrorKind::Io(err) if err.kind() == std::io::ErrorKind::InvalidData)
+        );
+        writer.send(11_u8).await.unwrap();
+        let (written, _) = writer.into_inner();
+        assert_eq!(written, b"\x09\n\x0B\n");
+
+        let reader =
+            TransmogReader::<_, u8, _>::new(&written[..], Bincode::default()).newline_delimited();
+        let read = reader.map(Result::unwrap).collect::<Vec<_>>().await;
+        assert_eq!(read, [9, 11]);
+    }
+
     #[tokio::test]
     async fn lots() {
         let echo = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
@@ -18,15 +18,28 @@ use transmog::OwnedDeserializer;
 /// [`Stream`] to access the deserialized values.
 ///
 /// Note that the sender *must* prefix each serialized item with its size
-/// encoded using [`ordered-varint`](ordered_varint).
+/// encoded using [`ordered-varint`](ordered_varint), unless the reader is
+/// configured using [`newline_delimited()`](Self::newline_delimited).
 #[derive(Debug)]
 pub struct TransmogReader<R, T, F> {
     format: F,
     reader: R,
     pub(crate) buffer: BytesMut,
+    pub(crate) framing: Framing,
+    /// The number of buffered bytes already known not to contain a newline.
+    scanned: usize,
     into: PhantomData<T>,
 }
 
+/// How values are separated from one another in the stream.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Framing {
+    /// Each value is prefixed with its size.
+    LengthPrefixed,
+    /// Each value is terminated by a newline.
+    NewlineDelimited,
+}
+
 impl<R, T, F> Unpin for TransmogReader<R, T, F> where R: Unpin {}
 
 impl<R, T, F> TransmogReader<R, T, F> {
@@ -65,11 +78,32 @@ impl<R, T, F> TransmogReader<R, T, F> {
         TransmogReader {
             format,
             buffer: BytesMut::with_capacity(8192),
+            framing: Framing::LengthPrefixed,
+            scanned: 0,
             reader,
             into: PhantomData,
         }
     }
 
+    /// Returns this instance configured to read values that are each
+    /// terminated by a newline (`\n`) rather than prefixed with their size,
+    /// such as [JSON Lines](https://jsonlines.org/).
+    ///
+    /// Every line is deserialized as one value, including empty lines, which
+    /// is how a writer configured with
+    /// [`for_newline_delimited()`](crate::TransmogWriter::for_newline_delimited)
+    /// writes values whose serialized form is empty. A `\r` before a newline
+    /// is ignored, and the final value does not need to be terminated.
+    ///
+    /// The format must never produce a newline within a serialized value.
+    /// To skip the blank lines some NDJSON producers emit, use a format that
+    /// does so itself, such as `transmog_json::JsonLines::read_lines`.
+    #[must_use]
+    pub fn newline_delimited(mut self) -> Self {
+        self.framing = Framing::NewlineDelimited;
+        self
+    }
+
     /// Returns a new instance that reads `format`-encoded data for `R::default()`.
     pub fn default_for(format: F) -> Self
     where
@@ -86,6 +120,10 @@ where
 {
     type Item = Result<T, F::Error>;
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.framing == Framing::NewlineDelimited {
+            return self.poll_next_line(cx);
+        }
+
         loop {
             let fill_result = ready!(self
                 .as_mut()
@@ -118,6 +156,51 @@ where
     }
 }
 
+impl<R, T, F> TransmogReader<R, T, F>
+where
+    R: AsyncRead + Unpin,
+    F: OwnedDeserializer<T>,
+{
+    fn poll_next_line(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<T, F::Error>>> {
+        loop {
+            let scanned = self.scanned;
+            if let Some(offset) = self.buffer[scanned..].iter().position(|b| *b == b'\n') {
+                let line_end = scanned + offset;
+                let line = self.buffer.split_to(line_end + 1);
+                self.scanned = 0;
+                break Poll::Ready(Some(self.deserialize_line(&line[..line_end])));
+            }
+            // Remember how much has been scanned, so that a line arriving
+            // across many reads is only scanned once.
+            self.scanned = self.buffer.len();
+
+            let target_buffer_size = self.buffer.len() + 1;
+            let fill_result = ready!(self
+                .as_mut()
+                .fill(cx, target_buffer_size)
+                .map_err(<F::Error as From<std::io::Error>>::from))?;
+            if let ReadResult::Eof = fill_result {
+                // The final line doesn't need to be terminated.
+                if self.buffer.is_empty() {
+                    break Poll::Ready(None);
+                }
+                let line = self.buffer.split();
+                self.scanned = 0;
+                break Poll::Ready(Some(self.deserialize_line(&line)));
+            }
+        }
+    }
+
+    /// Deserializes `line`, which has had its `\n` removed.
+    fn deserialize_line(&self, line: &[u8]) -> Result<T, F::Error> {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        self.format.deserialize_owned(line)
+    }
+}
+
 #[derive(Debug)]
 enum ReadResult {
     ReceivedData,
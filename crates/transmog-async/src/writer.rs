@@ -118,6 +118,34 @@ impl<W, T, F> TransmogWriter<W, T, AsyncDestination, F> {
     }
 }
 
+impl<W, T, F> TransmogWriter<W, T, SyncDestination, F> {
+    /// Make this writer terminate each serialized value with a newline
+    /// (`\n`) instead of prefixing it with its size.
+    ///
+    /// This is necessary for compatability with a
+    /// [`TransmogReader`](super::TransmogReader) configured with
+    /// [`newline_delimited()`](super::TransmogReader::newline_delimited), and
+    /// with other consumers of newline-delimited data such as [JSON
+    /// Lines](https://jsonlines.org/).
+    ///
+    /// Only formats that never produce a newline within a serialized value,
+    /// such as compact JSON, should be used. Sending a value whose serialized
+    /// form contains a newline (`0x0A`) fails with an error of kind
+    /// [`std::io::ErrorKind::InvalidData`], and the value is not written.
+    pub fn for_newline_delimited(self) -> TransmogWriter<W, T, NewlineDelimitedDestination, F> {
+        self.make_for()
+    }
+}
+
+impl<W, T, F> TransmogWriter<W, T, NewlineDelimitedDestination, F> {
+    /// Make this writer only send Transmog-encoded values.
+    ///
+    /// This is necessary for compatability with stock Transmog receivers.
+    pub fn for_sync(self) -> TransmogWriter<W, T, SyncDestination, F> {
+        self.make_for()
+    }
+}
+
 /// A marker that indicates that the wrapping type is compatible with [`TransmogReader`](super::TransmogReader).
 #[derive(Debug)]
 pub struct AsyncDestination;
@@ -126,6 +154,13 @@ pub struct AsyncDestination;
 #[derive(Debug)]
 pub struct SyncDestination;
 
+/// A marker that indicates that the wrapping type terminates each value with a
+/// newline, which is compatible with a
+/// [`newline_delimited()`](super::TransmogReader::newline_delimited)
+/// [`TransmogReader`](super::TransmogReader).
+#[derive(Debug)]
+pub struct NewlineDelimitedDestination;
+
 #[doc(hidden)]
 pub trait TransmogWriterFor<T, F>
 where
@@ -171,6 +206,27 @@ where
     }
 }
 
+impl<W, T, F> TransmogWriterFor<T, F> for TransmogWriter<W, T, NewlineDelimitedDestination, F>
+where
+    F: Format<'static, T>,
+{
+    fn append(&mut self, item: &T) -> Result<(), F::Error> {
+        let start = self.buffer.len();
+        self.format.serialize_into(item, &mut self.buffer)?;
+        // A newline within the value would be read back as the end of the
+        // value, so the value is discarded rather than corrupting the stream.
+        if self.buffer[start..].contains(&b'\n') {
+            self.buffer.truncate(start);
+            return Err(F::Error::from(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "serialized value contains a newline",
+            )));
+        }
+        self.buffer.push(b'\n');
+        Ok(())
+    }
+}
+
 impl<W, T, D, F> Sink<T> for TransmogWriter<W, T, D, F>
 where
     F: Format<'static, T>,
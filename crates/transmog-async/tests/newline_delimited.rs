@@ -0,0 +1,47 @@
+//! These tests use `transmog-json`, whose `serde_json` dependency adds
+//! `PartialEq` implementations that would make integer comparisons in the
+//! crate's unit tests ambiguous.
+
+use futures::prelude::*;
+use transmog_async::{TransmogReader, TransmogStream};
+use transmog_json::{Json, JsonLines};
+
+#[tokio::test]
+async fn newline_delimited() {
+    let echo = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = echo.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = echo.accept().await.unwrap();
+        let mut stream = TransmogStream::<String, String, _, _, _>::new(stream, JsonLines)
+            .for_newline_delimited();
+        let (r, w) = stream.tcp_split();
+        r.forward(w).await.unwrap();
+    });
+
+    let client = tokio::net::TcpStream::connect(&addr).await.unwrap();
+    let mut client = TransmogStream::build(client, Json::default())
+        .sends_and_receives::<String>()
+        .for_newline_delimited();
+
+    for value in ["hello", "multi\nline", &"a".repeat(100_000)] {
+        client.send(value.to_string()).await.unwrap();
+        assert_eq!(client.next().await.unwrap().unwrap(), value);
+    }
+}
+
+#[tokio::test]
+async fn ndjson_reader() {
+    let ndjson: &[u8] = b"{\"id\":1}\r\n\n{\"id\":2}\nnope\n{\"id\":3}";
+    let mut reader =
+        TransmogReader::<_, transmog_json::serde_json::Value, _>::new(ndjson, Json::default())
+            .newline_delimited();
+
+    assert_eq!(reader.next().await.unwrap().unwrap()["id"], 1);
+    // Every line is a value, so a blank line is an error for JSON.
+    assert!(reader.next().await.unwrap().is_err());
+    assert_eq!(reader.next().await.unwrap().unwrap()["id"], 2);
+    assert!(reader.next().await.unwrap().is_err());
+    assert_eq!(reader.next().await.unwrap().unwrap()["id"], 3);
+    assert!(reader.next().await.is_none());
+}
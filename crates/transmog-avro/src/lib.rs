@@ -0,0 +1,197 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+};
+
+pub use apache_avro;
+use apache_avro::Schema;
+use serde::{de::DeserializeOwned, Serialize};
+pub use transmog;
+use transmog::{Format, OwnedDeserializer};
+
+/// Avro implementor of [`Format`].
+///
+/// Values are serialized as single Avro datums using the writer schema. When
+/// deserializing, data is decoded using the writer schema and, if one has been
+/// provided, resolved against the reader schema using Avro's schema resolution
+/// rules.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Avro {
+    writer_schema: Arc<Schema>,
+    reader_schema: Option<Arc<Schema>>,
+}
+
+impl Avro {
+    /// Returns an instance that reads and writes data using `schema`.
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            writer_schema: Arc::new(schema),
+            reader_schema: None,
+        }
+    }
+
+    /// Parses `schema` and returns an instance that reads and writes data
+    /// using it.
+    pub fn parse(schema: &str) -> Result<Self, Error> {
+        Ok(Self::new(Schema::parse_str(schema)?))
+    }
+
+    /// Returns an instance that resolves values against `schema` when
+    /// deserializing data written with this instance's writer schema.
+    pub fn with_reader_schema(mut self, schema: Schema) -> Self {
+        self.reader_schema = Some(Arc::new(schema));
+        self
+    }
+
+    /// Parses `schema` and returns an instance that resolves values against it
+    /// when deserializing data written with this instance's writer schema.
+    pub fn with_reader_schema_str(self, schema: &str) -> Result<Self, Error> {
+        Ok(self.with_reader_schema(Schema::parse_str(schema)?))
+    }
+
+    /// Returns the schema used to serialize values and to decode serialized
+    /// data.
+    #[must_use]
+    pub fn writer_schema(&self) -> &Schema {
+        &self.writer_schema
+    }
+
+    /// Returns the schema deserialized values are resolved against, if one
+    /// has been provided.
+    #[must_use]
+    pub fn reader_schema(&self) -> Option<&Schema> {
+        self.reader_schema.as_deref()
+    }
+}
+
+impl<T> Format<'_, T> for Avro
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        let value = apache_avro::to_value(value)?.resolve(&self.writer_schema)?;
+        apache_avro::to_avro_datum(&self.writer_schema, value).map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        let bytes = self.serialize(value)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Avro
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let value = apache_avro::from_avro_datum(
+            &self.writer_schema,
+            &mut reader,
+            self.reader_schema.as_deref(),
+        )?;
+        apache_avro::from_value(&value).map_err(Error::from)
+    }
+}
+
+/// Errors from [`Avro`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred from parsing a schema, encoding, decoding, or
+    /// resolving a value.
+    #[error("avro error: {0}")]
+    Avro(Box<apache_avro::Error>),
+    /// An Io error occurred outside of `Avro`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<apache_avro::Error> for Error {
+    fn from(err: apache_avro::Error) -> Self {
+        Self::Avro(Box::new(err))
+    }
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Avro::parse(r#""long""#).unwrap());
+}
+
+#[test]
+fn schema_resolution() {
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Version1 {
+        id: i32,
+        tags: Vec<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Version2 {
+        id: i64,
+        tags: Vec<String>,
+        name: String,
+    }
+
+    let v1 = Avro::parse(
+        r#"{"type": "record", "name": "Event", "fields": [
+            {"name": "id", "type": "int"},
+            {"name": "tags", "type": {"type": "array", "items": "string"}}
+        ]}"#,
+    )
+    .unwrap();
+    let original = Version1 {
+        id: 1,
+        tags: vec![String::from("a"), String::from("b")],
+    };
+    let serialized = v1.serialize(&original).unwrap();
+    let deserialized: Version1 = v1.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, original);
+
+    let v2 = v1
+        .clone()
+        .with_reader_schema_str(
+            r#"{"type": "record", "name": "Event", "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "tags", "type": {"type": "array", "items": "string"}},
+                {"name": "name", "type": "string", "default": "unknown"}
+            ]}"#,
+        )
+        .unwrap();
+    let resolved: Version2 = v2.deserialize_owned(&serialized).unwrap();
+    assert_eq!(
+        resolved,
+        Version2 {
+            id: 1,
+            tags: original.tags,
+            name: String::from("unknown"),
+        }
+    );
+
+    // A reader schema that is incompatible with the writer schema fails to
+    // resolve.
+    let incompatible = v1.with_reader_schema_str(r#""string""#).unwrap();
+    assert!(matches!(
+        OwnedDeserializer::<String>::deserialize_owned(&incompatible, &serialized),
+        Err(Error::Avro(_))
+    ));
+}
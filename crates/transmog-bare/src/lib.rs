@@ -0,0 +1,130 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::io::{Read, Write};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+pub use serde_bare;
+pub use transmog;
+use transmog::{BorrowedDeserializer, Format, OwnedDeserializer};
+
+/// BARE implementor of [`Format`].
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct Bare;
+
+impl<T> Format<'_, T> for Bare
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialized_size(&self, value: &T) -> Result<Option<usize>, Self::Error> {
+        let mut counter = ByteCounter::default();
+        serde_bare::to_writer(&mut counter, value)?;
+        Ok(Some(counter.bytes_written))
+    }
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_bare::to_vec(value).map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, writer: W) -> Result<(), Self::Error> {
+        serde_bare::to_writer(writer, value).map_err(Error::from)
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for Bare
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        serde_bare::from_slice(data).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Bare
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        serde_bare::from_slice(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, reader: R) -> Result<T, Self::Error> {
+        serde_bare::from_reader(reader).map_err(Error::from)
+    }
+}
+
+/// A writer that only counts the bytes written to it.
+#[derive(Default)]
+struct ByteCounter {
+    bytes_written: usize,
+}
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.bytes_written += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Errors from [`Bare`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred from serializing or deserializing.
+    #[error("bare error: {0}")]
+    Bare(#[from] serde_bare::Error),
+    /// An Io error occurred outside of `Bare`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Bare);
+}
+
+#[test]
+fn messages() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Message<'a> {
+        id: u32,
+        body: &'a str,
+        attachments: Vec<Vec<u8>>,
+    }
+
+    let message = Message {
+        id: 1,
+        body: "hello",
+        attachments: vec![vec![1, 2, 3]],
+    };
+    let serialized = Bare.serialize(&message).unwrap();
+    assert_eq!(
+        serialized.len(),
+        Bare.serialized_size(&message).unwrap().unwrap()
+    );
+    let deserialized: Message<'_> = Bare.deserialize_borrowed(&serialized).unwrap();
+    assert_eq!(deserialized, message);
+
+    assert!(matches!(
+        OwnedDeserializer::<String>::deserialize_owned(&Bare, &[2, 0xFF, 0xFF]),
+        Err(Error::Bare(_))
+    ));
+}
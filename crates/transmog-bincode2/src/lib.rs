@@ -0,0 +1,396 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+pub use bincode;
+use bincode::{
+    config::{BigEndian, Config, Configuration, Fixint, Limit, LittleEndian, NoLimit, Varint},
+    enc::write::SizeWriter,
+    BorrowDecode, Decode, Encode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+pub use transmog;
+use transmog::{BorrowedDeserializer, Format, OwnedDeserializer};
+
+/// Bincode 2 implementor of [`Format`].
+///
+/// `Api` selects which traits values must implement: [`Native`] uses
+/// bincode's [`Encode`], [`Decode`], and [`BorrowDecode`] traits, while
+/// [`Serde`] uses serde's [`Serialize`] and [`Deserialize`] traits. `C` is the
+/// bincode [`Config`] used to encode and decode values.
+#[derive(Clone, Copy)]
+#[must_use]
+pub struct Bincode<Api = Native, C = Configuration> {
+    config: C,
+    reject_trailing_bytes: bool,
+    api: PhantomData<Api>,
+}
+
+/// Selects bincode's native [`Encode`], [`Decode`], and [`BorrowDecode`]
+/// traits for [`Bincode`].
+#[derive(Clone, Copy, Debug)]
+pub struct Native;
+
+/// Selects serde's [`Serialize`] and [`Deserialize`] traits for [`Bincode`].
+#[derive(Clone, Copy, Debug)]
+pub struct Serde;
+
+impl Default for Bincode {
+    /// Returns a `Bincode` instance using the native API and the equivalent of
+    /// [`bincode::config::standard()`].
+    fn default() -> Self {
+        Self {
+            config: bincode::config::standard(),
+            reject_trailing_bytes: true,
+            api: PhantomData,
+        }
+    }
+}
+
+impl Bincode {
+    /// Returns a `Bincode` instance using the native API and the equivalent of
+    /// [`bincode::config::legacy()`], which matches the encoding of bincode
+    /// 1's global serialization functions.
+    pub fn legacy_default() -> Bincode<Native, Configuration<LittleEndian, Fixint, NoLimit>> {
+        Self::default()
+            .with_config(bincode::config::legacy())
+            .allow_trailing_bytes()
+    }
+}
+
+impl<Api, C> Bincode<Api, C> {
+    /// Configures bincode to use serde's [`Serialize`] and [`Deserialize`]
+    /// traits.
+    pub fn serde(self) -> Bincode<Serde, C> {
+        Bincode {
+            config: self.config,
+            reject_trailing_bytes: self.reject_trailing_bytes,
+            api: PhantomData,
+        }
+    }
+
+    /// Configures bincode to use its native [`Encode`], [`Decode`], and
+    /// [`BorrowDecode`] traits.
+    pub fn native(self) -> Bincode<Native, C> {
+        Bincode {
+            config: self.config,
+            reject_trailing_bytes: self.reject_trailing_bytes,
+            api: PhantomData,
+        }
+    }
+
+    /// Replaces the bincode configuration with `config`.
+    pub fn with_config<NewConfig>(self, config: NewConfig) -> Bincode<Api, NewConfig> {
+        self.map_config(|_| config)
+    }
+
+    fn map_config<NewConfig>(self, map: impl FnOnce(C) -> NewConfig) -> Bincode<Api, NewConfig> {
+        Bincode {
+            config: map(self.config),
+            reject_trailing_bytes: self.reject_trailing_bytes,
+            api: PhantomData,
+        }
+    }
+
+    /// Configures Bincode to allow trailing bytes when deserializing.
+    pub fn allow_trailing_bytes(mut self) -> Self {
+        self.reject_trailing_bytes = false;
+        self
+    }
+
+    /// Configures Bincode to reject trailing bytes when deserializing. When
+    /// deserializing from a reader, the reader must reach its end after the
+    /// value has been decoded.
+    pub fn reject_trailing_bytes(mut self) -> Self {
+        self.reject_trailing_bytes = true;
+        self
+    }
+}
+
+impl<Api, E, I, L> Bincode<Api, Configuration<E, I, L>> {
+    /// Configures big-endian encoding. See [`Configuration::with_big_endian`]
+    /// for more information.
+    pub fn big_endian(self) -> Bincode<Api, Configuration<BigEndian, I, L>> {
+        self.map_config(Configuration::with_big_endian)
+    }
+
+    /// Configures little-endian encoding. See
+    /// [`Configuration::with_little_endian`] for more information.
+    pub fn little_endian(self) -> Bincode<Api, Configuration<LittleEndian, I, L>> {
+        self.map_config(Configuration::with_little_endian)
+    }
+
+    /// Configures variable length integer encoding. See
+    /// [`Configuration::with_variable_int_encoding`] for more information.
+    pub fn variable_integer_encoding(self) -> Bincode<Api, Configuration<E, Varint, L>> {
+        self.map_config(Configuration::with_variable_int_encoding)
+    }
+
+    /// Configures fixed length integer encoding. See
+    /// [`Configuration::with_fixed_int_encoding`] for more information.
+    pub fn fixed_integer_encoding(self) -> Bincode<Api, Configuration<E, Fixint, L>> {
+        self.map_config(Configuration::with_fixed_int_encoding)
+    }
+
+    /// Configures bincode to restrict decoding to `BYTE_LIMIT` bytes. See
+    /// [`Configuration::with_limit`] for more information.
+    pub fn limit<const BYTE_LIMIT: usize>(
+        self,
+    ) -> Bincode<Api, Configuration<E, I, Limit<BYTE_LIMIT>>> {
+        self.map_config(Configuration::with_limit::<BYTE_LIMIT>)
+    }
+
+    /// Configures no byte limit. See [`Configuration::with_no_limit`] for more
+    /// information.
+    pub fn no_limit(self) -> Bincode<Api, Configuration<E, I, NoLimit>> {
+        self.map_config(Configuration::with_no_limit)
+    }
+}
+
+impl<Api, C> Bincode<Api, C> {
+    fn check_trailing_bytes(&self, bytes_read: usize, data: &[u8]) -> Result<(), Error> {
+        if self.reject_trailing_bytes && bytes_read < data.len() {
+            Err(Error::TrailingBytes)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_trailing_reader<R: Read>(&self, mut reader: R) -> Result<(), Error> {
+        if self.reject_trailing_bytes && reader.read(&mut [0])? > 0 {
+            Err(Error::TrailingBytes)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T, C> Format<'_, T> for Bincode<Native, C>
+where
+    T: Encode,
+    C: Config + Send + Sync,
+{
+    type Error = Error;
+
+    fn serialized_size(&self, value: &T) -> Result<Option<usize>, Self::Error> {
+        let mut writer = SizeWriter::default();
+        bincode::encode_into_writer(value, &mut writer, self.config)?;
+        Ok(Some(writer.bytes_written))
+    }
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        bincode::encode_to_vec(value, self.config).map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        bincode::encode_into_std_write(value, &mut writer, self.config)?;
+        Ok(())
+    }
+}
+
+impl<'a, T, C> BorrowedDeserializer<'a, T> for Bincode<Native, C>
+where
+    T: Encode + BorrowDecode<'a, ()>,
+    C: Config + Send + Sync,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        let (value, bytes_read) = bincode::borrow_decode_from_slice(data, self.config)?;
+        self.check_trailing_bytes(bytes_read, data)?;
+        Ok(value)
+    }
+}
+
+impl<T, C> OwnedDeserializer<T> for Bincode<Native, C>
+where
+    T: Encode + Decode<()>,
+    C: Config + Send + Sync,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        let (value, bytes_read) = bincode::decode_from_slice(data, self.config)?;
+        self.check_trailing_bytes(bytes_read, data)?;
+        Ok(value)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let value = bincode::decode_from_std_read(&mut reader, self.config)?;
+        self.check_trailing_reader(reader)?;
+        Ok(value)
+    }
+}
+
+impl<T, C> Format<'_, T> for Bincode<Serde, C>
+where
+    T: Serialize,
+    C: Config + Send + Sync,
+{
+    type Error = Error;
+
+    fn serialized_size(&self, value: &T) -> Result<Option<usize>, Self::Error> {
+        let mut writer = SizeWriter::default();
+        bincode::serde::encode_into_writer(value, &mut writer, self.config)?;
+        Ok(Some(writer.bytes_written))
+    }
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        bincode::serde::encode_to_vec(value, self.config).map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        bincode::serde::encode_into_std_write(value, &mut writer, self.config)?;
+        Ok(())
+    }
+}
+
+impl<'a, T, C> BorrowedDeserializer<'a, T> for Bincode<Serde, C>
+where
+    T: Serialize + Deserialize<'a>,
+    C: Config + Send + Sync,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        let (value, bytes_read) = bincode::serde::borrow_decode_from_slice(data, self.config)?;
+        self.check_trailing_bytes(bytes_read, data)?;
+        Ok(value)
+    }
+}
+
+impl<T, C> OwnedDeserializer<T> for Bincode<Serde, C>
+where
+    T: Serialize + DeserializeOwned,
+    C: Config + Send + Sync,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        let (value, bytes_read) = bincode::serde::decode_from_slice(data, self.config)?;
+        self.check_trailing_bytes(bytes_read, data)?;
+        Ok(value)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let value = bincode::serde::decode_from_std_read(&mut reader, self.config)?;
+        self.check_trailing_reader(reader)?;
+        Ok(value)
+    }
+}
+
+/// Errors from [`Bincode`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred while encoding a value.
+    #[error("encode error: {0}")]
+    Encode(#[from] bincode::error::EncodeError),
+    /// An error occurred while decoding a value.
+    #[error("decode error: {0}")]
+    Decode(#[from] bincode::error::DecodeError),
+    /// Bytes remained after decoding a value while configured to reject
+    /// trailing bytes.
+    #[error("trailing bytes after decoded value")]
+    TrailingBytes,
+    /// An Io error occurred outside of encoding or decoding.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Bincode::default());
+    transmog::test_util::test_format(&Bincode::legacy_default());
+    transmog::test_util::test_format(&Bincode::default().serde());
+    transmog::test_util::test_format(&Bincode::default().big_endian().limit::<64>());
+    transmog::test_util::test_format(
+        &Bincode::default()
+            .serde()
+            .fixed_integer_encoding()
+            .little_endian()
+            .allow_trailing_bytes(),
+    );
+}
+
+#[test]
+fn configuration() {
+    let serialized = Bincode::default()
+        .big_endian()
+        .fixed_integer_encoding()
+        .serialize(&1_u32)
+        .unwrap();
+    assert_eq!(serialized, 1_u32.to_be_bytes());
+    let serialized = Bincode::default()
+        .serde()
+        .little_endian()
+        .variable_integer_encoding()
+        .serialize(&1_u32)
+        .unwrap();
+    assert_eq!(serialized, [1]);
+
+    let serialized = Bincode::default().serialize(&vec![0_u8; 64]).unwrap();
+    let limited = Bincode::default().limit::<16>();
+    assert!(matches!(
+        OwnedDeserializer::<Vec<u8>>::deserialize_owned(&limited, &serialized),
+        Err(Error::Decode(bincode::error::DecodeError::LimitExceeded))
+    ));
+    let unlimited = limited.no_limit();
+    let deserialized: Vec<u8> = unlimited.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized.len(), 64);
+}
+
+#[test]
+fn trailing_bytes() {
+    let bincode = Bincode::default();
+    let mut serialized = bincode.serialize(&1_u64).unwrap();
+    serialized.push(0);
+    assert!(matches!(
+        OwnedDeserializer::<u64>::deserialize_owned(&bincode, &serialized),
+        Err(Error::TrailingBytes)
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<u64>::deserialize_from(&bincode, &serialized[..]),
+        Err(Error::TrailingBytes)
+    ));
+    let bincode = bincode.allow_trailing_bytes();
+    let deserialized: u64 = bincode.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, 1);
+}
+
+#[test]
+fn borrowed_deserialization() {
+    use std::borrow::Cow;
+
+    #[derive(Encode, BorrowDecode)]
+    struct NativeTest<'a> {
+        value: &'a str,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct SerdeTest<'a> {
+        #[serde(borrow)]
+        value: Cow<'a, str>,
+    }
+
+    let bincode = Bincode::default();
+    let serialized = bincode.serialize(&NativeTest { value: "hello" }).unwrap();
+    let deserialized: NativeTest<'_> = bincode.deserialize_borrowed(&serialized).unwrap();
+    assert_eq!(deserialized.value, "hello");
+
+    let bincode = Bincode::default().serde();
+    let value = SerdeTest {
+        value: Cow::Owned(String::from("hello")),
+    };
+    let serialized = bincode.serialize(&value).unwrap();
+    let deserialized: SerdeTest<'_> = bincode.deserialize_borrowed(&serialized).unwrap();
+    assert_eq!(deserialized.value, "hello");
+    assert!(matches!(deserialized.value, Cow::Borrowed(_)));
+}
@@ -0,0 +1,193 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+pub use bitcode;
+use bitcode::{Decode, DecodeOwned, Encode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+pub use transmog;
+use transmog::{BorrowedDeserializer, Format, OwnedDeserializer};
+
+/// Bitcode implementor of [`Format`].
+///
+/// `Api` selects which traits values must implement: [`Native`] uses
+/// bitcode's [`Encode`] and [`Decode`] traits, while [`Serde`] uses serde's
+/// [`Serialize`] and [`Deserialize`] traits.
+#[derive(Clone, Copy)]
+#[must_use]
+pub struct Bitcode<Api = Native> {
+    api: PhantomData<Api>,
+}
+
+/// Selects bitcode's native [`Encode`] and [`Decode`] traits for [`Bitcode`].
+#[derive(Clone, Copy, Debug)]
+pub struct Native;
+
+/// Selects serde's [`Serialize`] and [`Deserialize`] traits for [`Bitcode`].
+#[derive(Clone, Copy, Debug)]
+pub struct Serde;
+
+impl Default for Bitcode {
+    /// Returns a `Bitcode` instance using the native API.
+    fn default() -> Self {
+        Self { api: PhantomData }
+    }
+}
+
+impl<Api> Bitcode<Api> {
+    /// Configures bitcode to use serde's [`Serialize`] and [`Deserialize`]
+    /// traits.
+    pub fn serde(self) -> Bitcode<Serde> {
+        Bitcode { api: PhantomData }
+    }
+
+    /// Configures bitcode to use its native [`Encode`] and [`Decode`] traits.
+    pub fn native(self) -> Bitcode<Native> {
+        Bitcode { api: PhantomData }
+    }
+}
+
+impl<T> Format<'_, T> for Bitcode<Native>
+where
+    T: Encode,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        Ok(bitcode::encode(value))
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        writer.write_all(&bitcode::encode(value))?;
+        Ok(())
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for Bitcode<Native>
+where
+    T: Encode + Decode<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        bitcode::decode(data).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Bitcode<Native>
+where
+    T: Encode + DecodeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        bitcode::decode(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        self.deserialize_owned(&data)
+    }
+}
+
+impl<T> Format<'_, T> for Bitcode<Serde>
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        bitcode::serialize(value).map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        writer.write_all(&bitcode::serialize(value)?)?;
+        Ok(())
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for Bitcode<Serde>
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        bitcode::deserialize(data).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Bitcode<Serde>
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        bitcode::deserialize(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        self.deserialize_owned(&data)
+    }
+}
+
+/// Errors from [`Bitcode`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred from encoding or decoding.
+    #[error("bitcode error: {0}")]
+    Bitcode(#[from] bitcode::Error),
+    /// An Io error occurred outside of `Bitcode`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Bitcode::default());
+    transmog::test_util::test_format(&Bitcode::default().serde());
+    transmog::test_util::test_format(&Bitcode::default().serde().native());
+}
+
+#[test]
+fn snapshots() {
+    #[derive(Encode, Decode, Serialize, Deserialize, Debug, PartialEq)]
+    struct Snapshot<'a> {
+        tick: u64,
+        name: &'a str,
+        positions: Vec<(f32, f32)>,
+    }
+
+    let snapshot = Snapshot {
+        tick: 1,
+        name: "snapshot",
+        positions: vec![(0., 1.), (2., 3.)],
+    };
+
+    let native = Bitcode::default();
+    let serialized = native.serialize(&snapshot).unwrap();
+    let deserialized: Snapshot<'_> = native.deserialize_borrowed(&serialized).unwrap();
+    assert_eq!(deserialized, snapshot);
+
+    let serde = Bitcode::default().serde();
+    let serialized = serde.serialize(&snapshot).unwrap();
+    let deserialized: Snapshot<'_> = serde.deserialize_borrowed(&serialized).unwrap();
+    assert_eq!(deserialized, snapshot);
+
+    assert!(matches!(
+        OwnedDeserializer::<Vec<u64>>::deserialize_owned(&native, &[255]),
+        Err(Error::Bitcode(_))
+    ));
+}
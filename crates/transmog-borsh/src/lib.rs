@@ -0,0 +1,111 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::io::{Read, Write};
+
+pub use borsh;
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use transmog;
+use transmog::{Format, OwnedDeserializer};
+
+/// Borsh implementor of [`Format`].
+#[derive(Clone, Default)]
+#[must_use]
+pub struct Borsh;
+
+impl<T> Format<'_, T> for Borsh
+where
+    T: BorshSerialize,
+{
+    type Error = Error;
+
+    fn serialized_size(&self, value: &T) -> Result<Option<usize>, Self::Error> {
+        borsh::object_length(value).map(Some).map_err(Error::from)
+    }
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        borsh::to_vec(value).map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, writer: W) -> Result<(), Self::Error> {
+        borsh::to_writer(writer, value).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Borsh
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        borsh::from_slice(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        borsh::from_reader(&mut reader).map_err(Error::from)
+    }
+}
+
+/// Errors from [`Borsh`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred from serializing or deserializing. `borsh` reports
+    /// invalid data as an error of kind [`std::io::ErrorKind::InvalidData`].
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Borsh);
+}
+
+#[test]
+fn structures() {
+    use std::collections::BTreeMap;
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+    struct Payload {
+        name: String,
+        attributes: BTreeMap<String, u32>,
+        signature: Option<[u8; 4]>,
+    }
+
+    let payload = Payload {
+        name: String::from("payload"),
+        attributes: [(String::from("b"), 2), (String::from("a"), 1)]
+            .into_iter()
+            .collect(),
+        signature: Some([1, 2, 3, 4]),
+    };
+    let serialized = Borsh.serialize(&payload).unwrap();
+    assert_eq!(
+        Borsh.serialized_size(&payload).unwrap(),
+        Some(serialized.len())
+    );
+    let deserialized: Payload = Borsh.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, payload);
+
+    // Trailing bytes are rejected to ensure only canonical payloads are accepted.
+    let mut trailing = serialized;
+    trailing.push(0);
+    assert!(matches!(
+        OwnedDeserializer::<Payload>::deserialize_owned(&Borsh, &trailing),
+        Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::InvalidData
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<Payload>::deserialize_from(&Borsh, &trailing[..]),
+        Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::InvalidData
+    ));
+}
@@ -0,0 +1,830 @@
+use std::{borrow::Cow, io};
+
+use serde::de::{self, Deserializer as _};
+
+/// The error type produced by [`Deserializer`], matching the error that
+/// `ciborium` produces when reading from an [`io::Read`] implementor.
+pub type DeError = ciborium::de::Error<io::Error>;
+
+const TAG_BIGPOS: u64 = 2;
+const TAG_BIGNEG: u64 = 3;
+
+const SIMPLE_FALSE: u8 = 20;
+const SIMPLE_TRUE: u8 = 21;
+const SIMPLE_NULL: u8 = 22;
+const SIMPLE_UNDEFINED: u8 = 23;
+
+/// Deserializes a `T` from the start of `data`, borrowing strings and byte
+/// strings from `data` whenever they are encoded with a definite length.
+pub fn from_slice<'de, T>(data: &'de [u8], recursion_limit: usize) -> Result<T, DeError>
+where
+    T: de::Deserialize<'de>,
+{
+    let mut deserializer = Deserializer {
+        reader: Reader::new(data),
+        recurse: recursion_limit,
+    };
+    T::deserialize(&mut deserializer)
+}
+
+/// A decoded CBOR data item header.
+#[derive(Debug, Clone, Copy)]
+pub enum Header {
+    Positive(u64),
+    Negative(u64),
+    Bytes(Option<usize>),
+    Text(Option<usize>),
+    Array(Option<usize>),
+    Map(Option<usize>),
+    Tag(u64),
+    Simple(u8),
+    Float(f64),
+    Break,
+}
+
+impl Header {
+    fn expected(self, kind: &'static str) -> DeError {
+        de::Error::invalid_type(
+            match self {
+                Header::Positive(x) => de::Unexpected::Unsigned(x),
+                Header::Negative(x) => match i64::try_from(x) {
+                    Ok(x) => de::Unexpected::Signed(x ^ !0),
+                    Err(_) => de::Unexpected::Other("negative integer"),
+                },
+                Header::Bytes(..) => de::Unexpected::Other("bytes"),
+                Header::Text(..) => de::Unexpected::Other("string"),
+                Header::Array(..) => de::Unexpected::Seq,
+                Header::Map(..) => de::Unexpected::Map,
+                Header::Tag(..) => de::Unexpected::Other("tag"),
+                Header::Simple(SIMPLE_FALSE) => de::Unexpected::Bool(false),
+                Header::Simple(SIMPLE_TRUE) => de::Unexpected::Bool(true),
+                Header::Simple(SIMPLE_NULL) => de::Unexpected::Other("null"),
+                Header::Simple(SIMPLE_UNDEFINED) => de::Unexpected::Other("undefined"),
+                Header::Simple(..) => de::Unexpected::Other("simple"),
+                Header::Float(x) => de::Unexpected::Float(x),
+                Header::Break => de::Unexpected::Other("break"),
+            },
+            &kind,
+        )
+    }
+}
+
+/// Reads CBOR data item headers and payloads from a slice.
+#[derive(Debug, Clone)]
+pub struct Reader<'de> {
+    data: &'de [u8],
+    offset: usize,
+}
+
+impl<'de> Reader<'de> {
+    pub fn new(data: &'de [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the bytes read since `offset`.
+    pub fn read_since(&self, offset: usize) -> &'de [u8] {
+        &self.data[offset..self.offset]
+    }
+
+    fn eof() -> DeError {
+        DeError::Io(io::Error::from(io::ErrorKind::UnexpectedEof))
+    }
+
+    /// Returns the next `len` bytes.
+    pub fn take(&mut self, len: usize) -> Result<&'de [u8], DeError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(Self::eof)?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    /// Reads the initial byte and argument of the next data item, returning
+    /// the major type, the additional information, and the argument. The
+    /// argument is `None` for indefinite lengths and breaks.
+    pub fn raw_header(&mut self) -> Result<(u8, u8, Option<u64>), DeError> {
+        let offset = self.offset;
+        let [initial] = self.array()?;
+        let major = initial >> 5;
+        let info = initial & 0x1f;
+        let argument = match info {
+            0..=23 => Some(u64::from(info)),
+            24 => Some(u64::from(u8::from_be_bytes(self.array()?))),
+            25 => Some(u64::from(u16::from_be_bytes(self.array()?))),
+            26 => Some(u64::from(u32::from_be_bytes(self.array()?))),
+            27 => Some(u64::from_be_bytes(self.array()?)),
+            31 if matches!(major, 2..=5 | 7) => None,
+            _ => return Err(DeError::Syntax(offset)),
+        };
+        Ok((major, info, argument))
+    }
+
+    /// Reads the header of the next data item.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn header(&mut self) -> Result<Header, DeError> {
+        let offset = self.offset;
+        let (major, info, argument) = self.raw_header()?;
+        let length = || {
+            argument
+                .map(usize::try_from)
+                .transpose()
+                .map_err(|_| DeError::Syntax(offset))
+        };
+        Ok(match (major, argument) {
+            (0, Some(value)) => Header::Positive(value),
+            (1, Some(value)) => Header::Negative(value),
+            (2, _) => Header::Bytes(length()?),
+            (3, _) => Header::Text(length()?),
+            (4, _) => Header::Array(length()?),
+            (5, _) => Header::Map(length()?),
+            (6, Some(tag)) => Header::Tag(tag),
+            (7, Some(value)) => match info {
+                // Each of these arguments was read from a field of the
+                // matching width.
+                25 => Header::Float(f16_to_f64(value as u16)),
+                26 => Header::Float(f64::from(f32::from_bits(value as u32))),
+                27 => Header::Float(f64::from_bits(value)),
+                // Simple values below 32 must be encoded in the initial byte.
+                24 if value < 32 => return Err(DeError::Syntax(offset)),
+                _ => Header::Simple(value as u8),
+            },
+            (7, None) => Header::Break,
+            _ => return Err(DeError::Syntax(offset)),
+        })
+    }
+
+    /// Returns the header of the next data item without consuming it.
+    pub fn peek(&mut self) -> Result<Header, DeError> {
+        let offset = self.offset;
+        let header = self.header();
+        self.offset = offset;
+        header
+    }
+
+    /// Reads the payload of a byte string whose header has been read,
+    /// borrowing it unless it is split into chunks.
+    pub fn bytes(&mut self, len: Option<usize>) -> Result<Cow<'de, [u8]>, DeError> {
+        if let Some(len) = len {
+            return self.take(len).map(Cow::Borrowed);
+        }
+
+        let mut bytes = Vec::new();
+        loop {
+            let offset = self.offset;
+            match self.header()? {
+                Header::Bytes(Some(len)) => bytes.extend_from_slice(self.take(len)?),
+                Header::Break => return Ok(Cow::Owned(bytes)),
+                _ => return Err(DeError::Syntax(offset)),
+            }
+        }
+    }
+
+    /// Reads the payload of a text string whose header has been read,
+    /// borrowing it unless it is split into chunks.
+    pub fn text(&mut self, len: Option<usize>) -> Result<Cow<'de, str>, DeError> {
+        if let Some(len) = len {
+            let offset = self.offset;
+            return std::str::from_utf8(self.take(len)?)
+                .map(Cow::Borrowed)
+                .map_err(|_| DeError::Syntax(offset));
+        }
+
+        let mut text = String::new();
+        loop {
+            let offset = self.offset;
+            match self.header()? {
+                Header::Text(Some(len)) => text.push_str(
+                    std::str::from_utf8(self.take(len)?).map_err(|_| DeError::Syntax(offset))?,
+                ),
+                Header::Break => return Ok(Cow::Owned(text)),
+                _ => return Err(DeError::Syntax(offset)),
+            }
+        }
+    }
+}
+
+/// Converts an IEEE 754 half-precision float to a double.
+pub fn f16_to_f64(bits: u16) -> f64 {
+    let exponent = (bits >> 10) & 0x1f;
+    let mantissa = f64::from(bits & 0x3ff);
+    let magnitude = match exponent {
+        0 => mantissa * 2_f64.powi(-24),
+        31 if mantissa == 0. => f64::INFINITY,
+        31 => f64::NAN,
+        exponent => (1024. + mantissa) * 2_f64.powi(i32::from(exponent) - 25),
+    };
+    if bits & 0x8000 == 0 {
+        magnitude
+    } else {
+        -magnitude
+    }
+}
+
+/// A CBOR deserializer that borrows from a slice. Values are interpreted the
+/// same way `ciborium`'s deserializer interprets them.
+struct Deserializer<'de> {
+    reader: Reader<'de>,
+    recurse: usize,
+}
+
+impl Deserializer<'_> {
+    fn recurse<V, F: FnOnce(&mut Self) -> Result<V, DeError>>(
+        &mut self,
+        func: F,
+    ) -> Result<V, DeError> {
+        if self.recurse == 0 {
+            return Err(DeError::RecursionLimitExceeded);
+        }
+
+        self.recurse -= 1;
+        let result = func(self);
+        self.recurse += 1;
+        result
+    }
+
+    /// Reads the next header, skipping any tags.
+    fn untagged_header(&mut self) -> Result<Header, DeError> {
+        loop {
+            let header = self.reader.header()?;
+            if !matches!(header, Header::Tag(..)) {
+                return Ok(header);
+            }
+        }
+    }
+
+    /// Reads an integer, including integers encoded as bignums.
+    fn integer(&mut self) -> Result<(bool, u128), DeError> {
+        loop {
+            let negative = match self.reader.header()? {
+                Header::Positive(x) => return Ok((false, x.into())),
+                Header::Negative(x) => return Ok((true, x.into())),
+                Header::Tag(TAG_BIGPOS) => false,
+                Header::Tag(TAG_BIGNEG) => true,
+                Header::Tag(..) => continue,
+                header => return Err(header.expected("integer")),
+            };
+
+            return match self.reader.header()? {
+                Header::Bytes(len) => {
+                    let bytes = self.reader.bytes(len)?;
+                    let first_significant =
+                        bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+                    let significant = &bytes[first_significant..];
+                    if significant.len() > 16 {
+                        return Err(de::Error::custom("bigint too large"));
+                    }
+                    let mut value = [0; 16];
+                    value[16 - significant.len()..].copy_from_slice(significant);
+                    Ok((negative, u128::from_be_bytes(value)))
+                }
+                header => Err(header.expected("bytes")),
+            };
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.reader.peek()? {
+            Header::Positive(..) => self.deserialize_u64(visitor),
+            Header::Negative(x) => match i64::try_from(x) {
+                Ok(..) => self.deserialize_i64(visitor),
+                Err(..) => self.deserialize_i128(visitor),
+            },
+
+            Header::Bytes(..) => self.deserialize_bytes(visitor),
+            Header::Text(..) => self.deserialize_str(visitor),
+            Header::Array(..) => self.deserialize_seq(visitor),
+            Header::Map(..) => self.deserialize_map(visitor),
+
+            Header::Tag(tag) => {
+                let start = self.reader.clone();
+                self.reader.header()?;
+                if matches!(tag, TAG_BIGPOS | TAG_BIGNEG)
+                    && matches!(self.reader.peek()?, Header::Bytes(Some(len)) if len <= 16)
+                {
+                    self.reader = start;
+                    return match self.integer()? {
+                        (false, raw) => visitor.visit_u128(raw),
+                        (true, raw) => match i128::try_from(raw) {
+                            Ok(x) => visitor.visit_i128(x ^ !0),
+                            Err(..) => Err(de::Error::custom("integer too large")),
+                        },
+                    };
+                }
+
+                self.recurse(|me| visitor.visit_enum(TagAccess::new(me, Some(tag))))
+            }
+
+            Header::Float(..) => self.deserialize_f64(visitor),
+
+            Header::Simple(SIMPLE_FALSE | SIMPLE_TRUE) => self.deserialize_bool(visitor),
+            Header::Simple(SIMPLE_NULL | SIMPLE_UNDEFINED) => self.deserialize_option(visitor),
+            header @ Header::Simple(..) => Err(header.expected("known simple value")),
+
+            header @ Header::Break => Err(header.expected("non-break")),
+        }
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let offset = self.reader.offset();
+        match self.untagged_header()? {
+            Header::Simple(SIMPLE_FALSE) => visitor.visit_bool(false),
+            Header::Simple(SIMPLE_TRUE) => visitor.visit_bool(true),
+            _ => Err(DeError::semantic(offset, "expected bool")),
+        }
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.untagged_header()? {
+            Header::Float(x) => visitor.visit_f64(x),
+            header => Err(header.expected("float")),
+        }
+    }
+
+    fn deserialize_i8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let result = match self.integer()? {
+            (false, raw) => i64::try_from(raw),
+            (true, raw) => i64::try_from(raw).map(|x| x ^ !0),
+        };
+
+        match result {
+            Ok(x) => visitor.visit_i64(x),
+            Err(..) => Err(de::Error::custom("integer too large")),
+        }
+    }
+
+    fn deserialize_i128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let result = match self.integer()? {
+            (false, raw) => i128::try_from(raw),
+            (true, raw) => i128::try_from(raw).map(|x| x ^ !0),
+        };
+
+        match result {
+            Ok(x) => visitor.visit_i128(x),
+            Err(..) => Err(de::Error::custom("integer too large")),
+        }
+    }
+
+    fn deserialize_u8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.integer()? {
+            (false, raw) => match u64::try_from(raw) {
+                Ok(x) => visitor.visit_u64(x),
+                Err(..) => Err(de::Error::custom("integer too large")),
+            },
+            (true, ..) => Err(de::Error::custom("unexpected negative integer")),
+        }
+    }
+
+    fn deserialize_u128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.integer()? {
+            (false, raw) => visitor.visit_u128(raw),
+            (true, ..) => Err(de::Error::custom("unexpected negative integer")),
+        }
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.untagged_header()? {
+            header @ Header::Text(len) => {
+                let text = self.reader.text(len)?;
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => visitor.visit_char(ch),
+                    _ => Err(header.expected("char")),
+                }
+            }
+            header => Err(header.expected("char")),
+        }
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.untagged_header()? {
+            Header::Text(len) => match self.reader.text(len)? {
+                Cow::Borrowed(text) => visitor.visit_borrowed_str(text),
+                Cow::Owned(text) => visitor.visit_string(text),
+            },
+            header => Err(header.expected("str")),
+        }
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.untagged_header()? {
+            Header::Bytes(len) => match self.reader.bytes(len)? {
+                Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+                Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+            },
+            Header::Array(len) => self.recurse(|me| visitor.visit_seq(Access(me, len))),
+            header => Err(header.expected("bytes")),
+        }
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.untagged_header()? {
+            Header::Array(len) => self.recurse(|me| visitor.visit_seq(Access(me, len))),
+            Header::Bytes(len) => {
+                let bytes = self.reader.bytes(len)?;
+                visitor.visit_seq(de::value::SeqDeserializer::new(bytes.iter().copied()))
+            }
+            header => Err(header.expected("array")),
+        }
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.untagged_header()? {
+            Header::Map(len) => self.recurse(|me| visitor.visit_map(Access(me, len))),
+            header => Err(header.expected("map")),
+        }
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.reader.peek()? {
+            Header::Bytes(..) => self.deserialize_bytes(visitor),
+            _ => self.deserialize_str(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.reader.peek()? {
+            Header::Simple(SIMPLE_NULL | SIMPLE_UNDEFINED) => {
+                self.reader.header()?;
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.untagged_header()? {
+            Header::Simple(SIMPLE_NULL | SIMPLE_UNDEFINED) => visitor.visit_unit(),
+            header => Err(header.expected("unit")),
+        }
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // `ciborium::tag`'s wrapper types request this name to capture tags.
+        if name == "@@TAG@@" {
+            let tag = match self.reader.peek()? {
+                Header::Tag(tag) => {
+                    self.reader.header()?;
+                    Some(tag)
+                }
+                _ => None,
+            };
+            return self.recurse(|me| visitor.visit_enum(TagAccess::new(me, tag)));
+        }
+
+        loop {
+            match self.reader.peek()? {
+                Header::Tag(..) => {
+                    self.reader.header()?;
+                    continue;
+                }
+                Header::Map(Some(1)) => {
+                    self.reader.header()?;
+                }
+                Header::Text(..) => {}
+                header => return Err(header.expected("enum")),
+            }
+
+            return self.recurse(|me| visitor.visit_enum(Access(me, Some(0))));
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Provides access to the entries of an array or map, or to an enum variant.
+struct Access<'a, 'de>(&'a mut Deserializer<'de>, Option<usize>);
+
+impl Access<'_, '_> {
+    /// Advances to the next entry, returning false if there are none left.
+    fn next_entry(&mut self) -> Result<bool, DeError> {
+        match self.1 {
+            Some(0) => Ok(false),
+            Some(remaining) => {
+                self.1 = Some(remaining - 1);
+                Ok(true)
+            }
+            None => match self.0.reader.peek()? {
+                Header::Break => {
+                    self.0.reader.header()?;
+                    Ok(false)
+                }
+                _ => Ok(true),
+            },
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Access<'_, 'de> {
+    type Error = DeError;
+
+    fn next_element_seed<U: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: U,
+    ) -> Result<Option<U::Value>, Self::Error> {
+        if self.next_entry()? {
+            seed.deserialize(&mut *self.0).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.1
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Access<'_, 'de> {
+    type Error = DeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        if self.next_entry()? {
+            seed.deserialize(&mut *self.0).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(&mut *self.0)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.1
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for Access<'_, 'de> {
+    type Error = DeError;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(&mut *self.0)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Access<'_, 'de> {
+    type Error = DeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<U: de::DeserializeSeed<'de>>(
+        self,
+        seed: U,
+    ) -> Result<U::Value, Self::Error> {
+        seed.deserialize(&mut *self.0)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_any(visitor)
+    }
+}
+
+/// Presents a tagged value as the enum that `ciborium::tag` and
+/// `ciborium::Value` expect: a `@@TAGGED@@` or `@@UNTAGGED@@` variant
+/// containing the tag, if any, followed by the value.
+struct TagAccess<'a, 'de> {
+    parent: Option<&'a mut Deserializer<'de>>,
+    state: usize,
+    tag: Option<u64>,
+}
+
+impl<'a, 'de> TagAccess<'a, 'de> {
+    fn new(parent: &'a mut Deserializer<'de>, tag: Option<u64>) -> Self {
+        Self {
+            parent: Some(parent),
+            state: 0,
+            tag,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut TagAccess<'_, 'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.state += 1;
+        match (self.state, self.tag) {
+            (1, Some(..)) => visitor.visit_str("@@TAGGED@@"),
+            (1, None) => visitor.visit_str("@@UNTAGGED@@"),
+            (_, Some(tag)) => visitor.visit_u64(tag),
+            (_, None) => Err(de::Error::custom("expected tag")),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128
+        u8 u16 u32 u64 u128
+        bool f32 f64
+        char str string
+        bytes byte_buf
+        seq map
+        struct tuple tuple_struct
+        identifier ignored_any
+        option unit unit_struct newtype_struct enum
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for TagAccess<'_, 'de> {
+    type Error = DeError;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        mut self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(&mut self)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for TagAccess<'_, 'de> {
+    type Error = DeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Err(de::Error::custom("expected tag"))
+    }
+
+    fn newtype_variant_seed<U: de::DeserializeSeed<'de>>(
+        mut self,
+        seed: U,
+    ) -> Result<U::Value, Self::Error> {
+        match self.parent.take() {
+            Some(parent) => seed.deserialize(parent),
+            None => Err(de::Error::custom("expected tag")),
+        }
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("expected tag"))
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for TagAccess<'_, 'de> {
+    type Error = DeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.state < 2 {
+            return Ok(Some(seed.deserialize(self)?));
+        }
+
+        match self.parent.take() {
+            Some(parent) => seed.deserialize(parent).map(Some),
+            None => Ok(None),
+        }
+    }
+}
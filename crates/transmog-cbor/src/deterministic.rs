@@ -0,0 +1,174 @@
+use ciborium::Value;
+
+use crate::{
+    de::{f16_to_f64, DeError, Reader},
+    Error,
+};
+
+/// Rewrites `value` so that serializing it produces the core deterministic
+/// encoding described in [RFC 8949 §4.2.1][rfc]: every map's entries are
+/// ordered by the bytewise lexicographic order of their encoded keys, and NaN
+/// is always encoded as the half-precision `0xf97e00`.
+///
+/// `ciborium` already writes integers, lengths, and floats in their shortest
+/// form, and writes every array and map in a [`Value`] with a definite length.
+///
+/// [rfc]: https://www.rfc-editor.org/rfc/rfc8949#section-4.2.1
+pub fn canonicalize(value: &mut Value) -> Result<(), Error> {
+    match value {
+        Value::Float(float) if float.is_nan() => *float = f64::NAN,
+        Value::Array(values) => {
+            for value in values {
+                canonicalize(value)?;
+            }
+        }
+        Value::Map(entries) => {
+            let mut encoded = Vec::with_capacity(entries.len());
+            for (mut key, mut value) in entries.drain(..) {
+                canonicalize(&mut key)?;
+                canonicalize(&mut value)?;
+                let mut key_bytes = Vec::new();
+                ciborium::ser::into_writer(&key, &mut key_bytes)?;
+                encoded.push((key_bytes, key, value));
+            }
+            encoded.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+            entries.extend(encoded.into_iter().map(|(_, key, value)| (key, value)));
+        }
+        Value::Tag(_, value) => canonicalize(value)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Checks that `data` contains exactly one data item that is encoded using
+/// the core deterministic encoding.
+pub fn verify(data: &[u8], recursion_limit: usize) -> Result<(), Error> {
+    let mut reader = Reader::new(data);
+    verify_item(&mut reader, recursion_limit)?;
+    if reader.offset() < data.len() {
+        return Err(not_deterministic(
+            reader.offset(),
+            "unexpected data after the item",
+        ));
+    }
+    Ok(())
+}
+
+fn not_deterministic(offset: usize, reason: &'static str) -> Error {
+    Error::NotDeterministic { offset, reason }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn verify_item(reader: &mut Reader<'_>, recursion_limit: usize) -> Result<(), Error> {
+    let recursion_limit = recursion_limit
+        .checked_sub(1)
+        .ok_or(DeError::RecursionLimitExceeded)?;
+    let offset = reader.offset();
+    let (major, info, argument) = reader.raw_header()?;
+    let Some(argument) = argument else {
+        return Err(if major == 7 {
+            Error::from(DeError::Syntax(offset))
+        } else {
+            not_deterministic(offset, "indefinite length")
+        });
+    };
+
+    if major == 7 {
+        // Each of these arguments was read from a field of the matching
+        // width.
+        let shortest = match info {
+            24 if argument < 32 => return Err(Error::from(DeError::Syntax(offset))),
+            25 => {
+                let float = f16_to_f64(argument as u16);
+                if float.is_nan() && argument != 0x7e00 {
+                    return Err(not_deterministic(offset, "NaN is not encoded as 0xf97e00"));
+                }
+                true
+            }
+            26 => {
+                let float = f64::from(f32::from_bits(argument as u32));
+                !float.is_nan() && !fits_f16(float)
+            }
+            27 => {
+                let float = f64::from_bits(argument);
+                !float.is_nan() && f64::from(float as f32).to_bits() != argument
+            }
+            _ => true,
+        };
+        return if shortest {
+            Ok(())
+        } else {
+            Err(not_deterministic(
+                offset,
+                "float is not in its shortest form",
+            ))
+        };
+    }
+
+    let shortest = match info {
+        0..=23 => true,
+        24 => argument >= 24,
+        25 => argument > 0xff,
+        26 => argument > 0xffff,
+        _ => argument > 0xffff_ffff,
+    };
+    if !shortest {
+        return Err(not_deterministic(
+            offset,
+            "argument is not in its shortest form",
+        ));
+    }
+
+    let length = || usize::try_from(argument).map_err(|_| DeError::Syntax(offset));
+    match major {
+        2 => {
+            reader.take(length()?)?;
+        }
+        3 => {
+            let text = reader.take(length()?)?;
+            std::str::from_utf8(text).map_err(|_| DeError::Syntax(offset))?;
+        }
+        4 => {
+            for _ in 0..argument {
+                verify_item(reader, recursion_limit)?;
+            }
+        }
+        5 => {
+            let mut previous_key = None;
+            for _ in 0..argument {
+                let key_offset = reader.offset();
+                verify_item(reader, recursion_limit)?;
+                let key = reader.read_since(key_offset);
+                if previous_key.is_some_and(|previous| previous >= key) {
+                    return Err(not_deterministic(
+                        key_offset,
+                        "map keys are not in ascending order",
+                    ));
+                }
+                previous_key = Some(key);
+                verify_item(reader, recursion_limit)?;
+            }
+        }
+        6 => verify_item(reader, recursion_limit)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Returns true if `value` can be represented exactly as a half-precision
+/// float.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn fits_f16(value: f64) -> bool {
+    let magnitude = value.abs();
+    if magnitude == 0. || magnitude.is_infinite() {
+        return true;
+    } else if magnitude > 65504. {
+        return false;
+    }
+
+    // Normal half-precision floats have 11 significant bits, and subnormal
+    // ones are multiples of 2^-24. Scaling by a power of two is exact.
+    let exponent = ((magnitude.to_bits() >> 52) & 0x7ff) as i32 - 1023;
+    let scale = if exponent < -14 { 24 } else { 10 - exponent };
+    (magnitude * 2_f64.powi(scale)).fract() == 0.
+}
@@ -0,0 +1,371 @@
+use std::fmt::Write;
+
+use crate::{
+    de::{f16_to_f64, DeError, Header, Reader},
+    Error, RECURSION_LIMIT,
+};
+
+/// A parsed data item, retaining the bytes it was encoded with.
+struct Item<'a> {
+    /// The major type of the item.
+    major: u8,
+    /// The initial byte and argument of the item.
+    header: &'a [u8],
+    kind: Kind<'a>,
+    /// The break that terminates an item with an indefinite length.
+    end: Option<&'a [u8]>,
+}
+
+enum Kind<'a> {
+    Unsigned(u64),
+    Negative(u64),
+    Bytes(&'a [u8]),
+    Text(&'a str),
+    /// A byte or text string with an indefinite length.
+    Chunks(Vec<Item<'a>>),
+    Array(Vec<Item<'a>>),
+    Map(Vec<(Item<'a>, Item<'a>)>),
+    Tag(u64, Box<Item<'a>>),
+    Simple(u8),
+    Float(f64),
+}
+
+/// Parses every data item in `data`.
+fn parse_sequence(data: &[u8]) -> Result<Vec<Item<'_>>, Error> {
+    let mut reader = Reader::new(data);
+    let mut items = Vec::new();
+    while reader.offset() < data.len() {
+        items.push(parse(&mut reader, RECURSION_LIMIT)?);
+    }
+    Ok(items)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn parse<'a>(reader: &mut Reader<'a>, recursion_limit: usize) -> Result<Item<'a>, Error> {
+    let recursion_limit = recursion_limit
+        .checked_sub(1)
+        .ok_or(DeError::RecursionLimitExceeded)?;
+    let offset = reader.offset();
+    let (major, info, argument) = reader.raw_header()?;
+    let header = reader.read_since(offset);
+    let length = |argument: u64| usize::try_from(argument).map_err(|_| DeError::Syntax(offset));
+
+    let mut end = None;
+    let kind = match (major, argument) {
+        (0, Some(value)) => Kind::Unsigned(value),
+        (1, Some(value)) => Kind::Negative(value),
+        (2, Some(len)) => Kind::Bytes(reader.take(length(len)?)?),
+        (3, Some(len)) => Kind::Text(
+            std::str::from_utf8(reader.take(length(len)?)?).map_err(|_| DeError::Syntax(offset))?,
+        ),
+        (2..=5, None) => {
+            let mut items = Vec::new();
+            loop {
+                let item_offset = reader.offset();
+                if matches!(reader.peek()?, Header::Break) {
+                    reader.header()?;
+                    end = Some(reader.read_since(item_offset));
+                    break;
+                }
+                let item = parse(reader, recursion_limit)?;
+                // The chunks of a string must be definite length strings of
+                // the same type.
+                if major < 4
+                    && (item.major != major || !matches!(item.kind, Kind::Bytes(_) | Kind::Text(_)))
+                {
+                    return Err(Error::from(DeError::Syntax(item_offset)));
+                }
+                items.push(item);
+            }
+            match major {
+                2 | 3 => Kind::Chunks(items),
+                4 => Kind::Array(items),
+                _ => Kind::Map(pair_entries(items, offset)?),
+            }
+        }
+        (4, Some(len)) => Kind::Array(
+            (0..len)
+                .map(|_| parse(reader, recursion_limit))
+                .collect::<Result<_, _>>()?,
+        ),
+        (5, Some(len)) => Kind::Map(
+            (0..len)
+                .map(|_| {
+                    Ok((
+                        parse(reader, recursion_limit)?,
+                        parse(reader, recursion_limit)?,
+                    ))
+                })
+                .collect::<Result<_, Error>>()?,
+        ),
+        (6, Some(tag)) => Kind::Tag(tag, Box::new(parse(reader, recursion_limit)?)),
+        // Each of these arguments was read from a field of the matching
+        // width.
+        (7, Some(value)) => match info {
+            24 if value < 32 => return Err(Error::from(DeError::Syntax(offset))),
+            25 => Kind::Float(f16_to_f64(value as u16)),
+            26 => Kind::Float(f64::from(f32::from_bits(value as u32))),
+            27 => Kind::Float(f64::from_bits(value)),
+            _ => Kind::Simple(value as u8),
+        },
+        _ => return Err(Error::from(DeError::Syntax(offset))),
+    };
+
+    Ok(Item {
+        major,
+        header,
+        kind,
+        end,
+    })
+}
+
+/// Pairs the items of an indefinite length map into its entries.
+fn pair_entries(items: Vec<Item<'_>>, offset: usize) -> Result<Vec<(Item<'_>, Item<'_>)>, Error> {
+    if !items.len().is_multiple_of(2) {
+        return Err(Error::from(DeError::Syntax(offset)));
+    }
+    let mut items = items.into_iter();
+    let mut entries = Vec::new();
+    while let (Some(key), Some(value)) = (items.next(), items.next()) {
+        entries.push((key, value));
+    }
+    Ok(entries)
+}
+
+/// Renders `data` in the diagnostic notation defined in [RFC 8949
+/// §8](https://www.rfc-editor.org/rfc/rfc8949#section-8).
+///
+/// If `data` contains more than one data item, such as a [CBOR
+/// Sequence](https://www.rfc-editor.org/rfc/rfc8742), the items are separated
+/// by commas.
+///
+/// ```rust
+/// use transmog::Format;
+/// use transmog_cbor::Cbor;
+///
+/// let data = Cbor::default().serialize(&(1, "two", [3.5])).unwrap();
+/// assert_eq!(
+///     transmog_cbor::diagnostic_notation(&data).unwrap(),
+///     r#"[1, "two", [3.5]]"#
+/// );
+/// ```
+pub fn diagnostic_notation(data: &[u8]) -> Result<String, Error> {
+    let mut notation = String::new();
+    for (index, item) in parse_sequence(data)?.iter().enumerate() {
+        if index > 0 {
+            notation.push_str(", ");
+        }
+        write_notation(item, &mut notation);
+    }
+    Ok(notation)
+}
+
+fn write_notation(item: &Item<'_>, out: &mut String) {
+    let indefinite = if item.end.is_some() { "_ " } else { "" };
+    match &item.kind {
+        Kind::Unsigned(value) => write!(out, "{value}").expect("infallible"),
+        Kind::Negative(value) => write!(out, "{}", -1 - i128::from(*value)).expect("infallible"),
+        Kind::Bytes(bytes) => {
+            out.push_str("h'");
+            for byte in *bytes {
+                write!(out, "{byte:02x}").expect("infallible");
+            }
+            out.push('\'');
+        }
+        Kind::Text(text) => write_quoted(text, out),
+        Kind::Chunks(chunks) if chunks.is_empty() => {
+            out.push_str(if item.major == 2 { "''_" } else { "\"\"_" });
+        }
+        Kind::Chunks(items) => {
+            out.push_str("(_ ");
+            write_separated(items, out);
+            out.push(')');
+        }
+        Kind::Array(items) => {
+            out.push('[');
+            out.push_str(indefinite);
+            write_separated(items, out);
+            out.push(']');
+        }
+        Kind::Map(entries) => {
+            out.push('{');
+            out.push_str(indefinite);
+            for (index, (key, value)) in entries.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_notation(key, out);
+                out.push_str(": ");
+                write_notation(value, out);
+            }
+            out.push('}');
+        }
+        Kind::Tag(tag, value) => {
+            write!(out, "{tag}(").expect("infallible");
+            write_notation(value, out);
+            out.push(')');
+        }
+        Kind::Simple(value) => out.push_str(&simple_name(*value)),
+        Kind::Float(value) => out.push_str(&format_float(*value)),
+    }
+}
+
+fn write_separated(items: &[Item<'_>], out: &mut String) {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            out.push_str(", ");
+        }
+        write_notation(item, out);
+    }
+}
+
+fn write_quoted(text: &str, out: &mut String) {
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => write!(out, "\\u{:04x}", u32::from(ch)).expect("infallible"),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+fn simple_name(value: u8) -> String {
+    match value {
+        20 => String::from("false"),
+        21 => String::from("true"),
+        22 => String::from("null"),
+        23 => String::from("undefined"),
+        value => format!("simple({value})"),
+    }
+}
+
+/// Formats `value` the way RFC 8949's examples do, such as `1.0`, `1.5`,
+/// `1.0e+300`, and `Infinity`.
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        return String::from("NaN");
+    } else if value.is_infinite() {
+        return String::from(if value > 0. { "Infinity" } else { "-Infinity" });
+    }
+
+    let formatted = format!("{value:?}");
+    match formatted.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let point = if mantissa.contains('.') { "" } else { ".0" };
+            let sign = if exponent.starts_with('-') { "" } else { "+" };
+            format!("{mantissa}{point}e{sign}{exponent}")
+        }
+        None => formatted,
+    }
+}
+
+/// Renders `data` as annotated hexadecimal, in the style of
+/// [cbor.me](https://cbor.me). Each line contains the bytes of one header or
+/// string payload, indented by nesting, followed by a description.
+///
+/// ```rust
+/// use transmog::Format;
+/// use transmog_cbor::Cbor;
+///
+/// let data = Cbor::default().serialize(&(1000, "a")).unwrap();
+/// assert_eq!(
+///     transmog_cbor::annotated_hex(&data).unwrap(),
+///     concat!(
+///         "82         # array(2)\n",
+///         "   19 03E8 # unsigned(1000)\n",
+///         "   61      # text(1)\n",
+///         "      61   # \"a\"\n",
+///     )
+/// );
+/// ```
+pub fn annotated_hex(data: &[u8]) -> Result<String, Error> {
+    let mut lines = Vec::new();
+    for item in &parse_sequence(data)? {
+        annotate(item, 0, &mut lines);
+    }
+
+    let width = lines.iter().map(|(hex, _)| hex.len()).max().unwrap_or(0);
+    let mut annotated = String::new();
+    for (hex, description) in lines {
+        writeln!(annotated, "{hex:width$} # {description}").expect("infallible");
+    }
+    Ok(annotated)
+}
+
+fn annotate(item: &Item<'_>, depth: usize, lines: &mut Vec<(String, String)>) {
+    let indent = "   ".repeat(depth);
+    let (initial, argument) = item.header.split_at(1);
+    let mut header = format!("{indent}{}", hex(initial));
+    if !argument.is_empty() {
+        header.push(' ');
+        header.push_str(&hex(argument));
+    }
+
+    let length = |len: usize| {
+        if item.end.is_some() {
+            String::from("*")
+        } else {
+            len.to_string()
+        }
+    };
+    let description = match &item.kind {
+        Kind::Unsigned(value) => format!("unsigned({value})"),
+        Kind::Negative(value) => format!("negative({})", -1 - i128::from(*value)),
+        Kind::Bytes(bytes) => format!("bytes({})", bytes.len()),
+        Kind::Text(text) => format!("text({})", text.len()),
+        Kind::Chunks(_) if item.major == 2 => String::from("bytes(*)"),
+        Kind::Chunks(_) => String::from("text(*)"),
+        Kind::Array(items) => format!("array({})", length(items.len())),
+        Kind::Map(entries) => format!("map({})", length(entries.len())),
+        Kind::Tag(tag, _) => format!("tag({tag})"),
+        Kind::Simple(value) => format!("primitive({})", simple_name(*value)),
+        Kind::Float(value) => format!("float({})", format_float(*value)),
+    };
+    lines.push((header, description));
+
+    match &item.kind {
+        Kind::Bytes(bytes) if !bytes.is_empty() => {
+            let mut quoted = String::from("\"");
+            for byte in bytes.iter().flat_map(|byte| byte.escape_ascii()) {
+                quoted.push(char::from(byte));
+            }
+            quoted.push('"');
+            lines.push((format!("{indent}   {}", hex(bytes)), quoted));
+        }
+        Kind::Text(text) if !text.is_empty() => {
+            let mut quoted = String::new();
+            write_quoted(text, &mut quoted);
+            lines.push((format!("{indent}   {}", hex(text.as_bytes())), quoted));
+        }
+        Kind::Chunks(items) | Kind::Array(items) => {
+            for item in items {
+                annotate(item, depth + 1, lines);
+            }
+        }
+        Kind::Map(entries) => {
+            for (key, value) in entries {
+                annotate(key, depth + 1, lines);
+                annotate(value, depth + 1, lines);
+            }
+        }
+        Kind::Tag(_, value) => annotate(value, depth + 1, lines),
+        _ => {}
+    }
+
+    if let Some(end) = item.end {
+        lines.push((format!("{indent}{}", hex(end)), String::from("break")));
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{byte:02X}").expect("infallible");
+        hex
+    })
+}
@@ -13,25 +13,269 @@
     clippy::option_if_let_else,
 )]
 
+mod de;
+mod deterministic;
+mod diag;
+mod sequence;
+
 use std::io::{Read, Write};
 
+pub use self::{
+    diag::{annotated_hex, diagnostic_notation},
+    sequence::{SequenceReader, SequenceWriter},
+};
 pub use ciborium;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 pub use transmog;
-use transmog::{Format, OwnedDeserializer};
+use transmog::{BorrowedDeserializer, Format, OwnedDeserializer};
+
+/// The recursion limit `ciborium` uses when deserializing.
+const RECURSION_LIMIT: usize = 256;
+
+/// The size of the scratch buffer `ciborium` uses when deserializing.
+const SCRATCH_BUFFER_SIZE: usize = 4096;
+
+/// The encoding of the self-described CBOR tag, 55799.
+const SELF_DESCRIBE_PREFIX: [u8; 3] = [0xd9, 0xd9, 0xf7];
 
 /// CBOR implementor of [`Format`].
-#[derive(Clone, Default)]
-pub struct Cbor;
+///
+/// Deserializing from a slice borrows text and byte strings from the slice
+/// when they are encoded with a definite length, which allows deserializing
+/// `&str`, `&[u8]` (using `serde_bytes`), and `Cow` fields without copying.
+///
+/// By default, deserialization fails on values nested more than 256 levels
+/// deep, and [`OwnedDeserializer::deserialize_from`] uses a 4KB scratch
+/// buffer. Only one of the two can be customized: setting either one restores
+/// the other's default.
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct Cbor {
+    deterministic: bool,
+    self_describe: bool,
+    tag: Option<u64>,
+    recursion_limit: usize,
+    scratch_buffer_size: usize,
+}
+
+impl Default for Cbor {
+    fn default() -> Self {
+        Self {
+            deterministic: false,
+            self_describe: false,
+            tag: None,
+            recursion_limit: RECURSION_LIMIT,
+            scratch_buffer_size: SCRATCH_BUFFER_SIZE,
+        }
+    }
+}
+
+impl Cbor {
+    /// Returns an instance that serializes using the core deterministic
+    /// encoding defined in [RFC 8949
+    /// §4.2.1](https://www.rfc-editor.org/rfc/rfc8949#section-4.2.1), which
+    /// guarantees that equal values produce identical bytes. This makes the
+    /// output suitable for hashing and signing.
+    ///
+    /// Integers, lengths, and floats are always written in their shortest
+    /// form. In deterministic mode, arrays and maps are also always written
+    /// with definite lengths, the entries of every map are ordered by the
+    /// bytewise lexicographic order of their encoded keys, and NaN is written
+    /// as `0xf97e00`. Values are converted to a [`ciborium::Value`] before
+    /// being written, which adds overhead.
+    ///
+    /// Deserialization is unaffected. Use [`Cbor::verify_deterministic`] to
+    /// reject input that is not deterministically encoded.
+    pub fn deterministic(mut self) -> Self {
+        self.deterministic = true;
+        self
+    }
+
+    /// Returns an instance that prefixes serialized values with the
+    /// self-described CBOR tag, 55799, which allows CBOR to be distinguished
+    /// from other formats by its first three bytes, `0xd9d9f7`.
+    ///
+    /// Deserialization always skips this prefix when it is present.
+    pub fn self_describe(mut self) -> Self {
+        self.self_describe = true;
+        self
+    }
+
+    /// Returns an instance that serializes values wrapped in `tag`, and that
+    /// requires deserialized values to be wrapped in `tag`.
+    ///
+    /// The tag is applied using [`ciborium::tag::Captured`]. To tag values
+    /// nested within other values, use the wrappers in [`ciborium::tag`]
+    /// directly.
+    pub fn tag(mut self, tag: u64) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    /// Returns an instance that fails to deserialize values nested more than
+    /// `limit` levels deep.
+    ///
+    /// The limit protects against overflowing the stack, and should only be
+    /// raised for trusted input.
+    ///
+    /// `ciborium` doesn't allow customizing both the recursion limit and the
+    /// scratch buffer of the same deserializer, so this restores the default
+    /// [`Cbor::scratch_buffer_size`].
+    pub fn recursion_limit(mut self, limit: usize) -> Self {
+        self.recursion_limit = limit;
+        self.scratch_buffer_size = SCRATCH_BUFFER_SIZE;
+        self
+    }
+
+    /// Returns an instance whose [`OwnedDeserializer::deserialize_from`] uses
+    /// a scratch buffer of `size` bytes, which is passed to
+    /// [`ciborium::de::from_reader_with_buffer`].
+    ///
+    /// Larger buffers require fewer reads, and text or byte strings that fit
+    /// within the buffer can be deserialized without allocating. Field names
+    /// and enum variants must fit within the buffer.
+    ///
+    /// `ciborium` doesn't allow customizing both the scratch buffer and the
+    /// recursion limit of the same deserializer, so this restores the default
+    /// [`Cbor::recursion_limit`].
+    pub fn scratch_buffer_size(mut self, size: usize) -> Self {
+        self.scratch_buffer_size = size;
+        self.recursion_limit = RECURSION_LIMIT;
+        self
+    }
+
+    /// Checks that `data` contains a single data item encoded using the core
+    /// deterministic encoding, returning [`Error::NotDeterministic`] if it
+    /// does not.
+    pub fn verify_deterministic(&self, data: &[u8]) -> Result<(), Error> {
+        deterministic::verify(data, self.recursion_limit)
+    }
+
+    /// Returns an iterator that deserializes each item of the [CBOR
+    /// Sequence](https://www.rfc-editor.org/rfc/rfc8742) in `reader`.
+    pub fn sequence_reader<T, R>(&self, reader: R) -> SequenceReader<R, T>
+    where
+        R: Read,
+    {
+        SequenceReader::new(*self, reader)
+    }
+
+    /// Returns a writer that appends items to the [CBOR
+    /// Sequence](https://www.rfc-editor.org/rfc/rfc8742) written to `writer`.
+    pub fn sequence_writer<W>(&self, writer: W) -> SequenceWriter<W>
+    where
+        W: Write,
+    {
+        SequenceWriter::new(*self, writer)
+    }
 
-impl<'a, T> Format<'a, T> for Cbor
+    fn serialize_value<T, W>(&self, value: &T, writer: W) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+        W: Write,
+    {
+        if self.deterministic {
+            let mut value = ciborium::Value::serialized(value)
+                .map_err(|err| ciborium::ser::Error::Value(err.to_string()))?;
+            deterministic::canonicalize(&mut value)?;
+            ciborium::ser::into_writer(&value, writer).map_err(Error::from)
+        } else {
+            ciborium::ser::into_writer(value, writer).map_err(Error::from)
+        }
+    }
+
+    fn deserialize_slice<'a, T>(&self, data: &'a [u8]) -> Result<T, Error>
+    where
+        T: Deserialize<'a>,
+    {
+        let data = data.strip_prefix(&SELF_DESCRIBE_PREFIX).unwrap_or(data);
+        if let Some(tag) = self.tag {
+            untag(de::from_slice(data, self.recursion_limit)?, tag)
+        } else {
+            de::from_slice(data, self.recursion_limit).map_err(Error::from)
+        }
+    }
+
+    fn deserialize_reader<T, R>(&self, reader: R) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+        R: Read,
+    {
+        // The setters ensure at most one of the two options is customized.
+        if self.recursion_limit == RECURSION_LIMIT {
+            let mut scratch = vec![0; self.scratch_buffer_size];
+            ciborium::de::from_reader_with_buffer(reader, &mut scratch).map_err(Error::from)
+        } else {
+            ciborium::de::from_reader_with_recursion_limit(reader, self.recursion_limit)
+                .map_err(Error::from)
+        }
+    }
+
+    /// Deserializes a value from `reader`, after any self-described CBOR
+    /// prefix has been removed.
+    fn deserialize_item<T, R>(&self, reader: R) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+        R: Read,
+    {
+        if let Some(tag) = self.tag {
+            untag(self.deserialize_reader(reader)?, tag)
+        } else {
+            self.deserialize_reader(reader)
+        }
+    }
+}
+
+/// Returns the value of `captured`, ensuring it was tagged with `tag`.
+fn untag<T>(captured: ciborium::tag::Captured<T>, tag: u64) -> Result<T, Error> {
+    match captured {
+        ciborium::tag::Captured(Some(actual), value) if actual == tag => Ok(value),
+        _ => Err(Error::Deserialization(ciborium::de::Error::semantic(
+            None,
+            format!("expected tag {tag}"),
+        ))),
+    }
+}
+
+/// Reads from `reader` until `buffer` is full or the end of the input is
+/// reached, returning the number of bytes read.
+fn read_up_to<R: Read>(reader: &mut R, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+impl<T> Format<'_, T> for Cbor
 where
     T: Serialize,
 {
     type Error = Error;
 
-    fn serialize_into<W: Write>(&self, value: &T, writer: W) -> Result<(), Self::Error> {
-        ciborium::ser::into_writer(value, writer).map_err(Error::from)
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        if self.self_describe {
+            writer.write_all(&SELF_DESCRIBE_PREFIX)?;
+        }
+        if let Some(tag) = self.tag {
+            self.serialize_value(&ciborium::tag::Captured(Some(tag), value), writer)
+        } else {
+            self.serialize_value(value, writer)
+        }
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for Cbor
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        self.deserialize_slice(data)
     }
 }
 
@@ -40,16 +284,24 @@ where
     T: Serialize + DeserializeOwned,
 {
     fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
-        self.deserialize_from(data)
+        self.deserialize_slice(data)
     }
 
-    fn deserialize_from<R: Read>(&self, reader: R) -> Result<T, Self::Error> {
-        ciborium::de::from_reader(reader).map_err(Error::from)
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut prefix = [0; SELF_DESCRIBE_PREFIX.len()];
+        let prefix_len = read_up_to(&mut reader, &mut prefix)?;
+        let prefix = if prefix == SELF_DESCRIBE_PREFIX {
+            &[][..]
+        } else {
+            &prefix[..prefix_len]
+        };
+        self.deserialize_item(prefix.chain(reader))
     }
 }
 
 /// CBOR serialization and deserialization errors.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// A serialization-related error.
     #[error("serialization error: {0}")]
@@ -57,6 +309,15 @@ pub enum Error {
     /// A deserialization-related error.
     #[error("serialization error: {0}")]
     Deserialization(#[from] ciborium::de::Error<std::io::Error>),
+    /// The data is valid CBOR, but is not encoded using the core deterministic
+    /// encoding.
+    #[error("not deterministically encoded at offset {offset}: {reason}")]
+    NotDeterministic {
+        /// The offset of the data item that is not deterministically encoded.
+        offset: usize,
+        /// The rule that the data item violates.
+        reason: &'static str,
+    },
 }
 
 impl From<std::io::Error> for Error {
@@ -67,5 +328,384 @@ impl From<std::io::Error> for Error {
 
 #[test]
 fn format_tests() {
-    transmog::test_util::test_format(&Cbor);
+    transmog::test_util::test_format(&Cbor::default());
+    transmog::test_util::test_format(&Cbor::default().deterministic());
+    transmog::test_util::test_format(&Cbor::default().self_describe().tag(1234));
+    transmog::test_util::test_format(&Cbor::default().recursion_limit(16));
+    transmog::test_util::test_format(&Cbor::default().scratch_buffer_size(64));
+}
+
+#[test]
+fn borrowed() {
+    use std::{borrow::Cow, collections::BTreeMap};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Message<'a> {
+        name: &'a str,
+        #[serde(with = "serde_bytes")]
+        payload: &'a [u8],
+        #[serde(borrow)]
+        note: Cow<'a, str>,
+        kind: Kind,
+        counts: BTreeMap<u8, i128>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Kind {
+        Unit,
+        Tuple(u8, Option<u16>),
+        Struct { value: f32 },
+    }
+
+    for kind in [
+        Kind::Unit,
+        Kind::Tuple(1, None),
+        Kind::Struct { value: 1.5 },
+    ] {
+        let message = Message {
+            name: "hello",
+            payload: b"world",
+            note: Cow::Borrowed("borrowed"),
+            kind,
+            counts: [(1, -1), (2, i128::MAX)].into_iter().collect(),
+        };
+        let serialized = Cbor::default().serialize(&message).unwrap();
+        let deserialized: Message<'_> = Cbor::default().deserialize_borrowed(&serialized).unwrap();
+        assert_eq!(deserialized, message);
+        assert!(matches!(deserialized.note, Cow::Borrowed(_)));
+
+        // The slice deserializer agrees with ciborium's reader.
+        let value: ciborium::Value = Cbor::default().deserialize_owned(&serialized).unwrap();
+        let from_reader: ciborium::Value = ciborium::de::from_reader(&serialized[..]).unwrap();
+        assert_eq!(value, from_reader);
+    }
+
+    // Text split into chunks with an indefinite length is copied.
+    let chunked = [0x7f, 0x62, b'a', b'b', 0x61, b'c', 0xff];
+    let text: Cow<'_, str> = Cbor::default().deserialize_borrowed(&chunked).unwrap();
+    assert_eq!(text, "abc");
+    assert!(matches!(
+        BorrowedDeserializer::<&str>::deserialize_borrowed(&Cbor::default(), &chunked),
+        Err(Error::Deserialization(_))
+    ));
+
+    // Half-precision floats and tagged values.
+    let half: f64 = Cbor::default()
+        .deserialize_owned(&[0xf9, 0x3e, 0x00])
+        .unwrap();
+    assert!((half - 1.5).abs() < f64::EPSILON);
+    let tagged: ciborium::tag::Required<u8, 1> = Cbor::default()
+        .deserialize_owned(&[0xc1, 0x18, 0x2a])
+        .unwrap();
+    assert_eq!(tagged.0, 42);
+
+    // Truncated input is reported as an unexpected EOF.
+    assert!(matches!(
+        OwnedDeserializer::<String>::deserialize_owned(&Cbor::default(), &[0x65, b'a']),
+        Err(Error::Deserialization(ciborium::de::Error::Io(err)))
+            if err.kind() == std::io::ErrorKind::UnexpectedEof
+    ));
+}
+
+#[test]
+fn deterministic() {
+    use std::collections::HashMap;
+
+    #[derive(Serialize)]
+    struct Flattened {
+        id: u8,
+        #[serde(flatten)]
+        extra: HashMap<String, f64>,
+    }
+
+    let value = Flattened {
+        id: 1,
+        extra: [("bb", 1.5), ("a", 100_000.5), ("ccc", f64::NAN)]
+            .into_iter()
+            .map(|(key, value)| (String::from(key), value))
+            .collect(),
+    };
+
+    // ciborium writes flattened structs as indefinite length maps.
+    let default = Cbor::default().serialize(&value).unwrap();
+    assert!(matches!(
+        Cbor::default().verify_deterministic(&default),
+        Err(Error::NotDeterministic { offset: 0, .. })
+    ));
+
+    let deterministic = Cbor::default().deterministic().serialize(&value).unwrap();
+    Cbor::default()
+        .verify_deterministic(&deterministic)
+        .unwrap();
+    assert_eq!(
+        deterministic,
+        [
+            0xa4, // map(4)
+            0x61, b'a', 0xfa, 0x47, 0xc3, 0x50, 0x40, // "a": 100000.5
+            0x62, b'b', b'b', 0xf9, 0x3e, 0x00, // "bb": 1.5
+            0x62, b'i', b'd', 0x01, // "id": 1
+            0x63, b'c', b'c', b'c', 0xf9, 0x7e, 0x00, // "ccc": NaN
+        ]
+    );
+
+    for (data, reason) in [
+        (&[0x18, 0x05][..], "argument is not in its shortest form"),
+        (&[0x9f, 0xff], "indefinite length"),
+        (
+            &[0xa2, 0x61, b'b', 0x01, 0x61, b'a', 0x02],
+            "map keys are not in ascending order",
+        ),
+        (
+            &[0xa2, 0x61, b'a', 0x01, 0x61, b'a', 0x02],
+            "map keys are not in ascending order",
+        ),
+        (
+            &[0xfa, 0x3f, 0xc0, 0x00, 0x00],
+            "float is not in its shortest form",
+        ),
+        (&[0xf9, 0x7e, 0x01], "NaN is not encoded as 0xf97e00"),
+        (&[0x01, 0x02], "unexpected data after the item"),
+    ] {
+        match Cbor::default().verify_deterministic(data) {
+            Err(Error::NotDeterministic { reason: actual, .. }) => assert_eq!(actual, reason),
+            other => unreachable!("{data:02x?} returned {other:?}"),
+        }
+    }
+    assert!(matches!(
+        Cbor::default().verify_deterministic(&[0x62, b'a']),
+        Err(Error::Deserialization(ciborium::de::Error::Io(_)))
+    ));
+}
+
+#[test]
+fn configuration() {
+    use ciborium::tag::Required;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Nested(Vec<Nested>);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Record {
+        a_long_field_name: u8,
+    }
+
+    // Self-described and tagged values.
+    let cbor = Cbor::default().self_describe().tag(1);
+    let serialized = cbor.serialize(&42_u8).unwrap();
+    assert_eq!(serialized, [0xd9, 0xd9, 0xf7, 0xc1, 0x18, 0x2a]);
+    let deserialized: u8 = cbor.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, 42);
+    let deserialized: u8 = cbor.deserialize_from(&serialized[..]).unwrap();
+    assert_eq!(deserialized, 42);
+    let value: ciborium::Value = Cbor::default().deserialize_from(&serialized[..]).unwrap();
+    assert_eq!(
+        value,
+        ciborium::Value::Tag(1, Box::new(ciborium::Value::from(42)))
+    );
+    assert!(matches!(
+        OwnedDeserializer::<u8>::deserialize_owned(&Cbor::default().tag(2), &serialized),
+        Err(Error::Deserialization(ciborium::de::Error::Semantic(..)))
+    ));
+
+    // Tagged values nested in other values.
+    let serialized = Cbor::default()
+        .serialize(&(Required::<&str, 32>("https://example.com"), 1_u8))
+        .unwrap();
+    assert_eq!(serialized[..3], [0x82, 0xd8, 0x20]);
+    let (url, _): (Required<&str, 32>, u8) =
+        Cbor::default().deserialize_borrowed(&serialized).unwrap();
+    assert_eq!(url.0, "https://example.com");
+
+    // Recursion limits.
+    let mut nested = Nested(Vec::new());
+    for _ in 0..10 {
+        nested = Nested(vec![nested]);
+    }
+    let serialized = Cbor::default().serialize(&nested).unwrap();
+    for cbor in [
+        Cbor::default().recursion_limit(10),
+        Cbor::default().scratch_buffer_size(64).recursion_limit(10),
+    ] {
+        assert!(matches!(
+            OwnedDeserializer::<Nested>::deserialize_owned(&cbor, &serialized),
+            Err(Error::Deserialization(
+                ciborium::de::Error::RecursionLimitExceeded
+            ))
+        ));
+        assert!(matches!(
+            OwnedDeserializer::<Nested>::deserialize_from(&cbor, &serialized[..]),
+            Err(Error::Deserialization(
+                ciborium::de::Error::RecursionLimitExceeded
+            ))
+        ));
+        let cbor = cbor.recursion_limit(11);
+        let deserialized: Nested = cbor.deserialize_from(&serialized[..]).unwrap();
+        assert_eq!(deserialized, nested);
+    }
+    assert!(Cbor::default()
+        .recursion_limit(10)
+        .verify_deterministic(&serialized)
+        .is_err());
+    // Customizing the scratch buffer restores the default recursion limit.
+    let cbor = Cbor::default().recursion_limit(10).scratch_buffer_size(64);
+    let deserialized: Nested = cbor.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, nested);
+    let deserialized: Nested = cbor.deserialize_from(&serialized[..]).unwrap();
+    assert_eq!(deserialized, nested);
+
+    // Field names must fit in the scratch buffer.
+    let serialized = Cbor::default()
+        .serialize(&Record {
+            a_long_field_name: 1,
+        })
+        .unwrap();
+    assert!(matches!(
+        OwnedDeserializer::<Record>::deserialize_from(
+            &Cbor::default().scratch_buffer_size(8),
+            &serialized[..]
+        ),
+        Err(Error::Deserialization(_))
+    ));
+    let record: Record = Cbor::default()
+        .scratch_buffer_size(32)
+        .deserialize_from(&serialized[..])
+        .unwrap();
+    assert_eq!(record.a_long_field_name, 1);
+}
+
+#[test]
+fn diagnostics() {
+    // Examples from RFC 8949 Appendix A.
+    for (hex, expected) in [
+        ("00", "0"),
+        ("1bffffffffffffffff", "18446744073709551615"),
+        ("3bffffffffffffffff", "-18446744073709551616"),
+        ("c249010000000000000000", "2(h'010000000000000000')"),
+        ("f90000", "0.0"),
+        ("f98000", "-0.0"),
+        ("fb3ff199999999999a", "1.1"),
+        ("f97bff", "65504.0"),
+        ("fa7f7fffff", "3.4028234663852886e+38"),
+        ("fb7e37e43c8800759c", "1.0e+300"),
+        ("f90001", "5.960464477539063e-8"),
+        ("f97c00", "Infinity"),
+        ("f97e00", "NaN"),
+        ("f9fc00", "-Infinity"),
+        ("f4", "false"),
+        ("f7", "undefined"),
+        ("f0", "simple(16)"),
+        ("f8ff", "simple(255)"),
+        (
+            "c074323031332d30332d32315432303a30343a30305a",
+            r#"0("2013-03-21T20:04:00Z")"#,
+        ),
+        (
+            "d82076687474703a2f2f7777772e6578616d706c652e636f6d",
+            r#"32("http://www.example.com")"#,
+        ),
+        ("40", "h''"),
+        ("4401020304", "h'01020304'"),
+        ("62225c", r#""\"\\""#),
+        ("63e6b0b4", r#""水""#),
+        ("a201020304", "{1: 2, 3: 4}"),
+        ("a26161016162820203", r#"{"a": 1, "b": [2, 3]}"#),
+        ("5f42010243030405ff", "(_ h'0102', h'030405')"),
+        ("7f657374726561646d696e67ff", r#"(_ "strea", "ming")"#),
+        ("9fff", "[_ ]"),
+        ("9f018202039f0405ffff", "[_ 1, [2, 3], [_ 4, 5]]"),
+        ("bf6346756ef563416d7421ff", r#"{_ "Fun": true, "Amt": -2}"#),
+        // A CBOR Sequence.
+        ("0102", "1, 2"),
+    ] {
+        let data = (0..hex.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostic_notation(&data).unwrap(), expected, "{hex}");
+    }
+
+    assert_eq!(
+        annotated_hex(&[0xbf, 0x61, b'a', 0xc1, 0x42, 0x00, 0xff, 0xff]).unwrap(),
+        concat!(
+            "BF            # map(*)\n",
+            "   61         # text(1)\n",
+            "      61      # \"a\"\n",
+            "   C1         # tag(1)\n",
+            "      42      # bytes(2)\n",
+            "         00FF # \"\\x00\\xff\"\n",
+            "FF            # break\n",
+        )
+    );
+
+    // Malformed input.
+    assert!(matches!(
+        diagnostic_notation(&[0x82, 0x01]),
+        Err(Error::Deserialization(ciborium::de::Error::Io(_)))
+    ));
+    assert!(matches!(
+        diagnostic_notation(&[0x5f, 0x61, b'a', 0xff]),
+        Err(Error::Deserialization(ciborium::de::Error::Syntax(1)))
+    ));
+    assert!(matches!(
+        diagnostic_notation(&[0xff]),
+        Err(Error::Deserialization(ciborium::de::Error::Syntax(0)))
+    ));
+}
+
+#[test]
+fn sequences() {
+    for cbor in [
+        Cbor::default(),
+        Cbor::default().self_describe().tag(1234),
+        Cbor::default().recursion_limit(16),
+        Cbor::default().scratch_buffer_size(64),
+    ] {
+        let mut writer = cbor.sequence_writer(Vec::new());
+        for value in [Some(1), None, Some(1000)] {
+            writer.write(&value).unwrap();
+        }
+        let data = writer.into_inner();
+
+        let values = cbor
+            .sequence_reader::<Option<u16>, _>(&data[..])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(values, [Some(1), None, Some(1000)]);
+
+        // Each item is a standalone value.
+        let first: Option<u16> = cbor.deserialize_from(&data[..]).unwrap();
+        assert_eq!(first, Some(1));
+
+        // Truncating the last item is an error rather than the end of the
+        // sequence.
+        let mut reader = cbor.sequence_reader::<Option<u16>, _>(&data[..data.len() - 1]);
+        assert_eq!(reader.next().unwrap().unwrap(), Some(1));
+        assert_eq!(reader.next().unwrap().unwrap(), None);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(Error::Deserialization(ciborium::de::Error::Io(err))))
+                if err.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    // Single byte items, which are shorter than the self-described CBOR
+    // prefix.
+    let values = Cbor::default()
+        .sequence_reader::<u8, _>(&[0x01, 0x02, 0x03, 0x04][..])
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, [1, 2, 3, 4]);
+
+    assert!(Cbor::default()
+        .sequence_reader::<u8, _>(&[][..])
+        .next()
+        .is_none());
+    assert!(matches!(
+        Cbor::default()
+            .sequence_reader::<u8, _>(&SELF_DESCRIBE_PREFIX[..])
+            .next(),
+        Some(Err(Error::Deserialization(ciborium::de::Error::Io(err))))
+            if err.kind() == std::io::ErrorKind::UnexpectedEof
+    ));
 }
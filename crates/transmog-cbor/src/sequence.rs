@@ -0,0 +1,144 @@
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use transmog::Format;
+
+use crate::{read_up_to, Cbor, Error, SELF_DESCRIBE_PREFIX};
+
+/// Reads a [CBOR Sequence](https://www.rfc-editor.org/rfc/rfc8742) of `T`
+/// values from `R`. Returned by [`Cbor::sequence_reader`].
+///
+/// Each item is deserialized the same way
+/// [`OwnedDeserializer::deserialize_from`](transmog::OwnedDeserializer::deserialize_from)
+/// deserializes a single value. The iterator returns `None` once the reader
+/// reaches the end of its input between two items. If the input ends partway
+/// through an item, an [`Error::Deserialization`] containing an
+/// [`std::io::ErrorKind::UnexpectedEof`] error is returned instead.
+///
+/// The position of the next item is unknown after an error, so the iterator
+/// returns `None` after returning an error.
+#[derive(Debug)]
+pub struct SequenceReader<R, T> {
+    format: Cbor,
+    reader: R,
+    /// Bytes that have been read from `reader` but not yet deserialized.
+    pending: Vec<u8>,
+    finished: bool,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<R, T> SequenceReader<R, T> {
+    pub(crate) fn new(format: Cbor, reader: R) -> Self {
+        Self {
+            format,
+            reader,
+            pending: Vec::with_capacity(SELF_DESCRIBE_PREFIX.len()),
+            finished: false,
+            _item: PhantomData,
+        }
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Up to three bytes of the next item may have already been read from
+    /// the reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R, T> SequenceReader<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    fn read_item(&mut self) -> Result<Option<T>, Error> {
+        // Read enough to detect the end of the input and the self-described
+        // CBOR prefix, keeping any bytes that belong to the item.
+        let filled = self.pending.len();
+        self.pending.resize(SELF_DESCRIBE_PREFIX.len(), 0);
+        let read = read_up_to(&mut self.reader, &mut self.pending[filled..])?;
+        self.pending.truncate(filled + read);
+        if self.pending.is_empty() {
+            return Ok(None);
+        } else if self.pending == SELF_DESCRIBE_PREFIX {
+            self.pending.clear();
+        }
+
+        // ciborium reads exactly the bytes that make up an item, leaving the
+        // rest of the sequence in the reader.
+        let mut remaining = &self.pending[..];
+        let value = self
+            .format
+            .deserialize_item((&mut remaining).chain(&mut self.reader));
+        let consumed = self.pending.len() - remaining.len();
+        self.pending.drain(..consumed);
+        value.map(Some)
+    }
+}
+
+impl<R, T> Iterator for SequenceReader<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let result = self.read_item();
+        self.finished = !matches!(result, Ok(Some(_)));
+        result.transpose()
+    }
+}
+
+/// Writes a [CBOR Sequence](https://www.rfc-editor.org/rfc/rfc8742) to `W`.
+/// Returned by [`Cbor::sequence_writer`].
+///
+/// Each item is serialized the same way [`Format::serialize_into`] serializes
+/// a single value, and is appended to the writer without any framing.
+#[derive(Debug)]
+pub struct SequenceWriter<W> {
+    format: Cbor,
+    writer: W,
+}
+
+impl<W> SequenceWriter<W> {
+    pub(crate) fn new(format: Cbor, writer: W) -> Self {
+        Self { format, writer }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> SequenceWriter<W>
+where
+    W: Write,
+{
+    /// Appends `value` to the sequence.
+    pub fn write<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize,
+    {
+        self.format.serialize_into(value, &mut self.writer)
+    }
+}
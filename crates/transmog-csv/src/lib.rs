@@ -0,0 +1,251 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::io::{Read, Write};
+
+pub use csv;
+use csv::{DeserializeRecordsIntoIter, QuoteStyle, ReaderBuilder, WriterBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+pub use transmog;
+use transmog::{Format, OwnedDeserializer};
+
+/// CSV implementor of [`Format`].
+///
+/// Unlike most formats, `Csv` serializes a sequence of records rather than a
+/// single value: [`Format`] and [`OwnedDeserializer`] are implemented for
+/// `Vec<T>`, where each `T` is one row. [`Csv::write_rows`] and
+/// [`Csv::read_rows`] can be used to write and read rows one at a time.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Csv {
+    delimiter: u8,
+    quote: u8,
+    quote_style: QuoteStyle,
+    headers: bool,
+}
+
+impl Default for Csv {
+    /// Returns an instance that separates fields with commas, quotes fields
+    /// with double quotes only when necessary, and writes and expects a header
+    /// row.
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            quote_style: QuoteStyle::Necessary,
+            headers: true,
+        }
+    }
+}
+
+impl Csv {
+    /// Returns an instance that separates fields with `delimiter`.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Returns an instance that quotes fields using `quote`.
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Returns an instance that uses `quote_style` to decide which fields are
+    /// quoted when serializing.
+    pub fn quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Returns an instance that neither writes nor expects a header row.
+    /// Records are written and read using the order of their fields rather
+    /// than their names.
+    pub fn without_headers(mut self) -> Self {
+        self.headers = false;
+        self
+    }
+
+    /// Serializes each row of `rows` into `writer`. If headers are enabled,
+    /// the header row is derived from the first row.
+    pub fn write_rows<I, W>(&self, rows: I, writer: W) -> Result<(), Error>
+    where
+        I: IntoIterator,
+        I::Item: Serialize,
+        W: Write,
+    {
+        let mut writer = self.writer_builder().from_writer(writer);
+        for row in rows {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns an iterator that deserializes one row at a time from `reader`.
+    pub fn read_rows<T, R>(&self, reader: R) -> RowReader<R, T>
+    where
+        T: DeserializeOwned,
+        R: Read,
+    {
+        RowReader {
+            rows: self.reader_builder().from_reader(reader).into_deserialize(),
+        }
+    }
+
+    fn writer_builder(&self) -> WriterBuilder {
+        let mut builder = WriterBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .quote_style(self.quote_style)
+            .has_headers(self.headers);
+        builder
+    }
+
+    fn reader_builder(&self) -> ReaderBuilder {
+        let mut builder = ReaderBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .has_headers(self.headers);
+        builder
+    }
+}
+
+impl<T> Format<'_, Vec<T>> for Csv
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &Vec<T>) -> Result<Vec<u8>, Self::Error> {
+        let mut bytes = Vec::new();
+        self.write_rows(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &Vec<T>, writer: W) -> Result<(), Self::Error> {
+        self.write_rows(value, writer)
+    }
+}
+
+impl<T> OwnedDeserializer<Vec<T>> for Csv
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_from<R: Read>(&self, reader: R) -> Result<Vec<T>, Self::Error> {
+        self.read_rows(reader).collect()
+    }
+}
+
+/// An iterator over rows deserialized from a reader. Returned from
+/// [`Csv::read_rows`].
+pub struct RowReader<R, T> {
+    rows: DeserializeRecordsIntoIter<R, T>,
+}
+
+impl<R, T> RowReader<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    /// Returns the position of the underlying reader.
+    #[must_use]
+    pub fn position(&self) -> &csv::Position {
+        self.rows.reader().position()
+    }
+}
+
+impl<R, T> Iterator for RowReader<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next().map(|row| row.map_err(Error::from))
+    }
+}
+
+/// Errors from [`Csv`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred from reading, writing, serializing, or deserializing
+    /// a row.
+    #[error("csv error: {0}")]
+    Csv(#[from] csv::Error),
+    /// An Io error occurred outside of `Csv`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn rows() {
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Sale {
+        region: String,
+        units: u32,
+        revenue: f64,
+    }
+
+    let sales = vec![
+        Sale {
+            region: String::from("North, East"),
+            units: 3,
+            revenue: 29.97,
+        },
+        Sale {
+            region: String::from("South"),
+            units: 1,
+            revenue: 9.99,
+        },
+    ];
+
+    let csv = Csv::default();
+    let serialized = csv.serialize(&sales).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&serialized).unwrap(),
+        "region,units,revenue\n\"North, East\",3,29.97\nSouth,1,9.99\n"
+    );
+    let deserialized: Vec<Sale> = csv.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, sales);
+
+    let mut streamed = Vec::new();
+    csv.write_rows(sales.iter().rev(), &mut streamed).unwrap();
+    let mut rows = csv.read_rows::<Sale, _>(&streamed[..]);
+    assert_eq!(rows.next().unwrap().unwrap(), sales[1]);
+    assert_eq!(rows.next().unwrap().unwrap(), sales[0]);
+    assert!(rows.next().is_none());
+
+    let tsv = Csv::default()
+        .delimiter(b'\t')
+        .quote(b'\'')
+        .quote_style(QuoteStyle::NonNumeric)
+        .without_headers();
+    let serialized = tsv.serialize(&sales).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&serialized).unwrap(),
+        "'North, East'\t3\t29.97\n'South'\t1\t9.99\n"
+    );
+    let deserialized: Vec<Sale> = tsv.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, sales);
+
+    let mut rows = csv.read_rows::<Sale, _>(&b"region,units,revenue\nWest,many,1.0\n"[..]);
+    assert!(matches!(rows.next(), Some(Err(Error::Csv(_)))));
+}
@@ -0,0 +1,136 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+    // flexbuffers 2.0.0, the latest release, depends on num_enum 0.5, which
+    // still uses syn 1.
+    clippy::multiple_crate_versions,
+)]
+
+use std::io::{Read, Write};
+
+pub use flexbuffers;
+use flexbuffers::Reader;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+pub use transmog;
+use transmog::{BorrowedDeserializer, Format, OwnedDeserializer};
+
+/// Flexbuffers implementor of [`Format`].
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct Flexbuffers;
+
+impl Flexbuffers {
+    /// Returns a [`Reader`] for the root value of `data`, which can be used to
+    /// inspect parts of a serialized value without deserializing all of it.
+    pub fn root<'a>(&self, data: &'a [u8]) -> Result<Reader<&'a [u8]>, Error> {
+        Reader::get_root(data).map_err(Error::from)
+    }
+}
+
+impl<T> Format<'_, T> for Flexbuffers
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        flexbuffers::to_vec(value).map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        writer.write_all(&flexbuffers::to_vec(value)?)?;
+        Ok(())
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for Flexbuffers
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        flexbuffers::from_slice(data).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Flexbuffers
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        flexbuffers::from_slice(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        // The root of a flexbuffer is stored at the end of the buffer, so the
+        // entire buffer must be read before deserializing.
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        self.deserialize_owned(&data)
+    }
+}
+
+/// Errors from [`Flexbuffers`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred while serializing a value.
+    #[error("serialization error: {0}")]
+    Serialization(#[from] flexbuffers::SerializationError),
+    /// An error occurred while deserializing a value.
+    #[error("deserialization error: {0}")]
+    Deserialization(#[from] flexbuffers::DeserializationError),
+    /// An error occurred while reading a buffer using [`Flexbuffers::root`].
+    #[error("reader error: {0}")]
+    Reader(#[from] flexbuffers::ReaderError),
+    /// An Io error occurred outside of `Flexbuffers`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Flexbuffers);
+}
+
+#[test]
+fn partial_inspection() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Document<'a> {
+        title: &'a str,
+        tags: Vec<&'a str>,
+        views: u64,
+    }
+
+    let document = Document {
+        title: "transmog",
+        tags: vec!["serialization", "flexbuffers"],
+        views: 42,
+    };
+    let serialized = Flexbuffers.serialize(&document).unwrap();
+
+    let deserialized: Document<'_> = Flexbuffers.deserialize_borrowed(&serialized).unwrap();
+    assert_eq!(deserialized, document);
+
+    let root = Flexbuffers.root(&serialized).unwrap();
+    let map = root.as_map();
+    assert_eq!(map.idx("views").as_u64(), 42);
+    assert_eq!(map.idx("title").as_str(), "transmog");
+    let tags = map.idx("tags").as_vector();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags.idx(1).as_str(), "flexbuffers");
+
+    assert!(matches!(Flexbuffers.root(&[0]), Err(Error::Reader(_))));
+    assert!(matches!(
+        OwnedDeserializer::<u64>::deserialize_owned(&Flexbuffers, &[0]),
+        Err(Error::Deserialization(_))
+    ));
+}
@@ -0,0 +1,173 @@
+use std::io::Write;
+
+use serde_json::{Number, Value};
+
+use crate::Error;
+
+/// Writes `value` using the JSON Canonicalization Scheme defined by
+/// [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785).
+pub fn write_value<W: Write>(value: &Value, writer: &mut W) -> Result<(), Error> {
+    match value {
+        Value::Null => writer.write_all(b"null")?,
+        Value::Bool(true) => writer.write_all(b"true")?,
+        Value::Bool(false) => writer.write_all(b"false")?,
+        Value::Number(number) => write_number(number, writer)?,
+        // serde_json's compact string escaping matches RFC 8785 §3.2.2.2: only
+        // quotation marks, reverse solidi, and control characters are
+        // escaped, using lowercase hexadecimal digits.
+        Value::String(string) => serde_json::to_writer(&mut *writer, string)?,
+        Value::Array(values) => {
+            writer.write_all(b"[")?;
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    writer.write_all(b",")?;
+                }
+                write_value(value, writer)?;
+            }
+            writer.write_all(b"]")?;
+        }
+        Value::Object(object) => {
+            // Keys are ordered by their UTF-16 code units, not by their UTF-8
+            // bytes or code points.
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            writer.write_all(b"{")?;
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    writer.write_all(b",")?;
+                }
+                serde_json::to_writer(&mut *writer, key)?;
+                writer.write_all(b":")?;
+                write_value(value, writer)?;
+            }
+            writer.write_all(b"}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes `number` as the IEEE 754 double it represents, returning an error
+/// if the conversion would lose precision.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn write_number<W: Write>(number: &Number, writer: &mut W) -> Result<(), Error> {
+    let value = if let Some(value) = number.as_u64() {
+        let double = value as f64;
+        if double as u128 != u128::from(value) {
+            return Err(Error::UnrepresentableNumber(number.clone()));
+        }
+        double
+    } else if let Some(value) = number.as_i64() {
+        let double = value as f64;
+        if double as i128 != i128::from(value) {
+            return Err(Error::UnrepresentableNumber(number.clone()));
+        }
+        double
+    } else {
+        number
+            .as_f64()
+            .ok_or_else(|| Error::UnrepresentableNumber(number.clone()))?
+    };
+    writer.write_all(format_double(value).as_bytes())?;
+    Ok(())
+}
+
+/// Formats `value` using the algorithm ECMAScript's `Number.prototype.toString`
+/// uses, as required by RFC 8785 §3.2.2.3.
+fn format_double(value: f64) -> String {
+    if value == 0. {
+        // Negative zero is serialized as `0`.
+        return String::from("0");
+    }
+
+    // Rust's exponential formatting produces the shortest digits that round
+    // trip, which is what ECMAScript requires. When two candidates with that
+    // many digits are equally close to the value, ECMAScript requires the even
+    // one, which formatting with an explicit precision produces.
+    let shortest = format!("{:e}", value.abs());
+    let precision = shortest.find('e').unwrap_or_default().saturating_sub(2);
+    let rounded = format!("{:.precision$e}", value.abs());
+    let scientific = if rounded.parse::<f64>() == Ok(value.abs()) {
+        rounded
+    } else {
+        shortest
+    };
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("exponential formatting always includes an exponent");
+    let digits = mantissa.replace('.', "");
+    let exponent = exponent
+        .parse::<i32>()
+        .expect("exponential formatting produces an integer exponent");
+    let digit_count = i32::try_from(digits.len()).expect("at most 17 digits");
+    // The position of the decimal point relative to the start of `digits`.
+    let point = exponent + 1;
+
+    let mut formatted = String::new();
+    if value.is_sign_negative() {
+        formatted.push('-');
+    }
+    if digit_count <= point && point <= 21 {
+        formatted.push_str(&digits);
+        formatted.extend((digit_count..point).map(|_| '0'));
+    } else if 0 < point && point <= 21 {
+        let (integer, fraction) = digits.split_at(point.unsigned_abs() as usize);
+        formatted.push_str(integer);
+        formatted.push('.');
+        formatted.push_str(fraction);
+    } else if -6 < point && point <= 0 {
+        formatted.push_str("0.");
+        formatted.extend((point..0).map(|_| '0'));
+        formatted.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        formatted.push_str(first);
+        if !rest.is_empty() {
+            formatted.push('.');
+            formatted.push_str(rest);
+        }
+        formatted.push('e');
+        if exponent > 0 {
+            formatted.push('+');
+        }
+        formatted.push_str(&exponent.to_string());
+    }
+    formatted
+}
+
+#[test]
+fn number_serialization() {
+    // The sample values from RFC 8785 Appendix B.
+    for (bits, expected) in [
+        (0x0000_0000_0000_0000_u64, "0"),
+        (0x8000_0000_0000_0000, "0"),
+        (0x0000_0000_0000_0001, "5e-324"),
+        (0x8000_0000_0000_0001, "-5e-324"),
+        (0x7fef_ffff_ffff_ffff, "1.7976931348623157e+308"),
+        (0xffef_ffff_ffff_ffff, "-1.7976931348623157e+308"),
+        (0x4340_0000_0000_0000, "9007199254740992"),
+        (0xc340_0000_0000_0000, "-9007199254740992"),
+        (0x4430_0000_0000_0000, "295147905179352830000"),
+        (0x44b5_2d02_c7e1_4af5, "9.999999999999997e+22"),
+        (0x44b5_2d02_c7e1_4af6, "1e+23"),
+        (0x44b5_2d02_c7e1_4af7, "1.0000000000000001e+23"),
+        (0x444b_1ae4_d6e2_ef4e, "999999999999999700000"),
+        (0x444b_1ae4_d6e2_ef4f, "999999999999999900000"),
+        (0x444b_1ae4_d6e2_ef50, "1e+21"),
+        (0x3eb0_c6f7_a0b5_ed8c, "9.999999999999997e-7"),
+        (0x3eb0_c6f7_a0b5_ed8d, "0.000001"),
+        (0x41b3_de43_5555_5553, "333333333.3333332"),
+        (0x41b3_de43_5555_5554, "333333333.33333325"),
+        (0x41b3_de43_5555_5555, "333333333.3333333"),
+        (0x41b3_de43_5555_5556, "333333333.3333334"),
+        (0x41b3_de43_5555_5557, "333333333.33333343"),
+        (0xbecb_f647_612f_3696, "-0.0000033333333333333333"),
+        (0x4314_3ff3_c1cb_0959, "1424953923781206.2"),
+    ] {
+        assert_eq!(format_double(f64::from_bits(bits)), expected, "{bits:x}");
+    }
+}
@@ -13,52 +13,398 @@
     clippy::option_if_let_else,
 )]
 
-use std::io::{Read, Write};
+mod canonical;
+mod pointer;
+#[cfg(feature = "schema")]
+mod schema;
+mod ser;
+mod stream;
 
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+#[cfg(feature = "schema")]
+use std::sync::Arc;
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+#[cfg(feature = "schema")]
+pub use jsonschema;
+#[cfg(feature = "schema")]
+pub use schemars;
+
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed},
+    Deserialize, Serialize,
+};
 pub use serde_json;
 pub use transmog;
 use transmog::{BorrowedDeserializer, Format, OwnedDeserializer};
 
+#[cfg(feature = "schema")]
+pub use self::schema::SchemaViolation;
+use self::ser::{JsonFormatter, Sorted};
+pub use self::stream::{ArrayReader, LineReader, ValueReader};
+
 /// Json implementor of [`Format`].
-#[derive(Clone, Default)]
+///
+/// By default, values are serialized compactly, and deserialization fails on
+/// values nested more than 128 levels deep.
+#[derive(Clone, Debug, Default)]
 #[must_use]
 pub struct Json {
-    pretty: bool,
+    indent: Option<String>,
+    sort_keys: bool,
+    ascii_only: bool,
+    unbounded_depth: bool,
+    #[cfg(feature = "schema")]
+    schema: Option<Arc<jsonschema::Validator>>,
 }
 
 impl Json {
-    /// Returns an instance configured to serialize in a "pretty" format.
-    pub fn pretty(mut self) -> Self {
-        self.pretty = true;
+    /// Returns an instance configured to serialize in a "pretty" format,
+    /// indenting using two spaces.
+    pub fn pretty(self) -> Self {
+        self.indent("  ")
+    }
+
+    /// Returns an instance configured to serialize in a "pretty" format,
+    /// indenting using `indent`, such as `"\t"`.
+    pub fn indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = Some(indent.into());
+        self
+    }
+
+    /// Returns an instance that serializes the entries of every object ordered
+    /// by key, regardless of the order the value's `Serialize` implementation
+    /// produces them in.
+    ///
+    /// This is useful for producing stable output from types such as
+    /// [`HashMap`](std::collections::HashMap). Values are converted to an
+    /// intermediate representation before being written, which adds overhead.
+    pub fn sort_keys(mut self) -> Self {
+        self.sort_keys = true;
+        self
+    }
+
+    /// Returns an instance that escapes every non-ASCII character in strings
+    /// using `\u` escape sequences.
+    pub fn ascii_only(mut self) -> Self {
+        self.ascii_only = true;
         self
     }
+
+    /// Returns an instance that deserializes values nested to any depth.
+    ///
+    /// `serde_json` normally limits the depth of nested values to protect
+    /// against overflowing the stack. With the limit disabled, the stack is
+    /// grown on the heap as needed using
+    /// [`serde_stacker`](https://docs.rs/serde_stacker). This should only be
+    /// used with trusted input, as memory use is only bounded by the input's
+    /// nesting.
+    pub fn disable_recursion_limit(mut self) -> Self {
+        self.unbounded_depth = true;
+        self
+    }
+
+    /// Returns an instance that validates input against the JSON Schema
+    /// `schema` before deserializing it.
+    ///
+    /// If the input does not match the schema, deserialization fails with
+    /// [`Error::SchemaValidation`], which lists every violation. Validation is
+    /// performed by [`BorrowedDeserializer`] and [`OwnedDeserializer`], and
+    /// requires parsing the input twice. `deserialize_from` reads the entire
+    /// input into memory before validating it.
+    ///
+    /// Requires the `schema` feature.
+    #[cfg(feature = "schema")]
+    pub fn validate_schema(mut self, schema: &serde_json::Value) -> Result<Self, Error> {
+        self.schema = Some(Arc::new(schema::compile(schema)?));
+        Ok(self)
+    }
+
+    /// Returns an instance that validates input against the JSON Schema that
+    /// [`schemars`] generates for `T` before deserializing it. See
+    /// [`Json::validate_schema`] for more information.
+    ///
+    /// Requires the `schema` feature.
+    #[cfg(feature = "schema")]
+    pub fn validate_schema_for<T: schemars::JsonSchema>(self) -> Result<Self, Error> {
+        self.validate_schema(schemars::schema_for!(T).as_value())
+    }
+
+    #[cfg(feature = "schema")]
+    fn validate(&self, data: &[u8]) -> Result<(), Error> {
+        if let Some(validator) = &self.schema {
+            let instance: serde_json::Value =
+                self.deserialize(serde_json::Deserializer::from_slice(data))?;
+            schema::validate(validator, &instance)?;
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over a sequence of JSON values in `reader`, such as
+    /// values that have been concatenated or separated by whitespace.
+    pub fn read_values<T, R>(&self, reader: R) -> ValueReader<R, T>
+    where
+        T: DeserializeOwned,
+        R: Read,
+    {
+        ValueReader::new(reader, self.unbounded_depth)
+    }
+
+    /// Returns an iterator over the elements of the JSON array in `reader`.
+    ///
+    /// Unlike deserializing a `Vec<T>`, only one element is held in memory at
+    /// a time, which allows processing arrays too large to fit in memory.
+    pub fn read_array<T, R>(&self, reader: R) -> ArrayReader<R, T>
+    where
+        T: DeserializeOwned,
+        R: Read,
+    {
+        ArrayReader::new(reader, self.clone())
+    }
+
+    /// Deserializes only the value addressed by the [RFC
+    /// 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer `pointer`,
+    /// such as `"/users/0/name"`. Returns `None` if `data` does not contain
+    /// the addressed value.
+    ///
+    /// Every other value in `data` is skipped without being deserialized or
+    /// allocated, and `T` may borrow from `data`. The entire document is
+    /// still validated.
+    pub fn deserialize_pointer<'a, T>(
+        &self,
+        data: &'a [u8],
+        pointer: &str,
+    ) -> Result<Option<T>, Error>
+    where
+        T: Deserialize<'a>,
+    {
+        let tokens = pointer::parse(pointer)?;
+        self.deserialize_seed(
+            pointer::PointerSeed::new(&tokens),
+            serde_json::Deserializer::from_slice(data),
+        )
+    }
+
+    fn deserialize<'de, T, R>(&self, deserializer: serde_json::Deserializer<R>) -> Result<T, Error>
+    where
+        T: Deserialize<'de>,
+        R: serde_json::de::Read<'de>,
+    {
+        self.deserialize_seed(PhantomData, deserializer)
+    }
+
+    fn deserialize_seed<'de, S, R>(
+        &self,
+        seed: S,
+        mut deserializer: serde_json::Deserializer<R>,
+    ) -> Result<S::Value, Error>
+    where
+        S: DeserializeSeed<'de>,
+        R: serde_json::de::Read<'de>,
+    {
+        let value = if self.unbounded_depth {
+            deserializer.disable_recursion_limit();
+            seed.deserialize(serde_stacker::Deserializer::new(&mut deserializer))?
+        } else {
+            seed.deserialize(&mut deserializer)?
+        };
+        deserializer.end()?;
+        Ok(value)
+    }
 }
 
-impl<'a, T> Format<'a, T> for Json
+impl<T> Format<'_, T> for Json
 where
     T: Serialize,
 {
     type Error = Error;
 
     fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
-        if self.pretty {
-            serde_json::to_vec_pretty(value).map_err(Error::from)
+        let mut bytes = Vec::with_capacity(128);
+        self.serialize_into(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, writer: W) -> Result<(), Self::Error> {
+        let formatter = JsonFormatter::new(self.indent.as_deref(), self.ascii_only);
+        let mut serializer = serde_json::Serializer::with_formatter(writer, formatter);
+        if self.sort_keys {
+            Sorted::new(value)?.serialize(&mut serializer)?;
         } else {
-            serde_json::to_vec(value).map_err(Error::from)
+            value.serialize(&mut serializer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for Json
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        #[cfg(feature = "schema")]
+        self.validate(data)?;
+        self.deserialize(serde_json::Deserializer::from_slice(data))
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Json
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        #[cfg(feature = "schema")]
+        self.validate(data)?;
+        self.deserialize(serde_json::Deserializer::from_slice(data))
+    }
+    fn deserialize_from<R: Read>(&self, reader: R) -> Result<T, Self::Error> {
+        #[cfg(feature = "schema")]
+        if self.schema.is_some() {
+            let mut reader = reader;
+            let mut data = Vec::new();
+            reader.read_to_end(&mut data)?;
+            return self.deserialize_owned(&data);
         }
+        self.deserialize(serde_json::Deserializer::from_reader(reader))
+    }
+}
+
+/// [JSON Lines](https://jsonlines.org/) implementor of [`Format`].
+///
+/// Each value is serialized as compact JSON, which never contains a newline.
+/// Use [`JsonLines::write_lines`] to write a JSON Lines document, and
+/// [`JsonLines::read_lines`] to iterate over the values of a document. To
+/// exchange JSON Lines asynchronously, use a newline-delimited
+/// `transmog-async` stream, which terminates each value with a newline.
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct JsonLines;
+
+impl JsonLines {
+    /// Serializes each value of `values` onto its own line of `writer`,
+    /// terminating each line with a newline.
+    pub fn write_lines<I, W>(&self, values: I, mut writer: W) -> Result<(), Error>
+    where
+        I: IntoIterator,
+        I::Item: Serialize,
+        W: Write,
+    {
+        for value in values {
+            self.serialize_into(&value, &mut writer)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator that deserializes one value from each line of
+    /// `reader`.
+    ///
+    /// Blank lines are skipped, and both `\n` and `\r\n` line endings are
+    /// accepted.
+    pub fn read_lines<T, R>(&self, reader: R) -> LineReader<R, T>
+    where
+        T: DeserializeOwned,
+        R: Read,
+    {
+        LineReader::new(reader)
+    }
+}
+
+impl<T> Format<'_, T> for JsonLines
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(value).map_err(Error::from)
     }
 
     fn serialize_into<W: Write>(&self, value: &T, writer: W) -> Result<(), Self::Error> {
-        if self.pretty {
-            serde_json::to_writer_pretty(writer, value).map_err(Error::from)
+        // Compact JSON escapes newlines within strings, so the value never
+        // contains a newline.
+        serde_json::to_writer(writer, value).map_err(Error::from)
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for JsonLines
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        serde_json::from_slice(data).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for JsonLines
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        serde_json::from_slice(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, reader: R) -> Result<T, Self::Error> {
+        serde_json::from_reader(reader).map_err(Error::from)
+    }
+}
+
+/// [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785) canonical JSON
+/// implementor of [`Format`].
+///
+/// Values are serialized using the JSON Canonicalization Scheme (JCS):
+/// without insignificant whitespace, with object entries ordered by the UTF-16
+/// code units of their keys, and with numbers and strings written using their
+/// canonical forms. Equal values always produce identical bytes, making the
+/// output suitable for hashing and signing.
+///
+/// Numbers are serialized as the IEEE 754 double they represent, and
+/// integers that cannot be represented exactly as a double cause
+/// serialization to fail with [`Error::UnrepresentableNumber`]. As with
+/// [`Json`], non-finite floating point values are serialized as `null`.
+///
+/// Deserialization accepts any JSON. Use [`CanonicalJson::verify`] to check
+/// that data is canonical.
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct CanonicalJson;
+
+impl CanonicalJson {
+    /// Checks that `data` is valid JSON in its canonical form, returning
+    /// [`Error::NotCanonical`] if serializing the value it contains would
+    /// produce different bytes.
+    pub fn verify(&self, data: &[u8]) -> Result<(), Error> {
+        let value: serde_json::Value = serde_json::from_slice(data)?;
+        let mut canonical = Vec::with_capacity(data.len());
+        canonical::write_value(&value, &mut canonical)?;
+        if canonical == data {
+            Ok(())
         } else {
-            serde_json::to_writer(writer, value).map_err(Error::from)
+            Err(Error::NotCanonical)
         }
     }
 }
 
-impl<'a, T> BorrowedDeserializer<'a, T> for Json
+impl<T> Format<'_, T> for CanonicalJson
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        let mut bytes = Vec::with_capacity(128);
+        self.serialize_into(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        canonical::write_value(&serde_json::to_value(value)?, &mut writer)
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for CanonicalJson
 where
     T: Serialize + Deserialize<'a>,
 {
@@ -67,13 +413,14 @@ where
     }
 }
 
-impl<T> OwnedDeserializer<T> for Json
+impl<T> OwnedDeserializer<T> for CanonicalJson
 where
     T: Serialize + DeserializeOwned,
 {
     fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
         serde_json::from_slice(data).map_err(Error::from)
     }
+
     fn deserialize_from<R: Read>(&self, reader: R) -> Result<T, Self::Error> {
         serde_json::from_reader(reader).map_err(Error::from)
     }
@@ -83,15 +430,442 @@ where
 fn format_tests() {
     transmog::test_util::test_format(&Json::default());
     transmog::test_util::test_format(&Json::default().pretty());
+    transmog::test_util::test_format(
+        &Json::default()
+            .indent("\t")
+            .sort_keys()
+            .ascii_only()
+            .disable_recursion_limit(),
+    );
+    transmog::test_util::test_format(&JsonLines);
+    transmog::test_util::test_format(&CanonicalJson);
 }
 
 /// Errors from [`Json`].
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// An error occurred from parsing `Json`.
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    /// The input did not match the JSON Schema configured using
+    /// [`Json::validate_schema`] or [`Json::validate_schema_for`].
+    #[cfg(feature = "schema")]
+    #[error("json schema validation failed: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    SchemaValidation(Vec<SchemaViolation>),
+    /// A JSON Schema could not be compiled.
+    #[cfg(feature = "schema")]
+    #[error("invalid json schema: {0}")]
+    InvalidSchema(String),
+    /// A JSON Pointer is not valid according to [RFC
+    /// 6901](https://www.rfc-editor.org/rfc/rfc6901).
+    #[error("invalid json pointer: {0:?}")]
+    InvalidPointer(String),
+    /// A number could not be represented exactly as an IEEE 754 double, which
+    /// [`CanonicalJson`] requires.
+    #[error("number cannot be represented canonically: {0}")]
+    UnrepresentableNumber(serde_json::Number),
+    /// The data passed to [`CanonicalJson::verify`] is valid JSON, but is not
+    /// in its canonical form.
+    #[error("json is not canonical")]
+    NotCanonical,
     /// An Io error occurred outside of parsing `Json`.
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+#[test]
+fn configuration() {
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize)]
+    struct Nested(Vec<Nested>);
+
+    #[derive(Serialize)]
+    enum Wide {
+        Values { z: u128, y: i128 },
+    }
+
+    let value = HashMap::from([("b", vec!["\u{e9}\u{1f600}"]), ("a", vec![])]);
+    assert_eq!(
+        Json::default()
+            .indent("\t")
+            .sort_keys()
+            .ascii_only()
+            .serialize(&value)
+            .unwrap(),
+        b"{\n\t\"a\": [],\n\t\"b\": [\n\t\t\"\\u00e9\\ud83d\\ude00\"\n\t]\n}"
+    );
+    assert_eq!(
+        Json::default().sort_keys().serialize(&value).unwrap(),
+        "{\"a\":[],\"b\":[\"\u{e9}\u{1f600}\"]}".as_bytes()
+    );
+
+    // Sorting supports the same values as serializing directly, including
+    // integers that don't fit in 64 bits.
+    let wide = HashMap::from([(
+        2_u8,
+        Wide::Values {
+            z: u128::MAX,
+            y: i128::MIN,
+        },
+    )]);
+    assert_eq!(
+        Json::default().sort_keys().serialize(&wide).unwrap(),
+        format!(
+            "{{\"2\":{{\"Values\":{{\"y\":{},\"z\":{}}}}}}}",
+            i128::MIN,
+            u128::MAX
+        )
+        .as_bytes()
+    );
+
+    let deep = format!("{}{}", "[".repeat(1_000), "]".repeat(1_000));
+    assert!(matches!(
+        OwnedDeserializer::<Nested>::deserialize_owned(&Json::default(), deep.as_bytes()),
+        Err(Error::Json(_))
+    ));
+    let nested: Nested = Json::default()
+        .disable_recursion_limit()
+        .deserialize_from(deep.as_bytes())
+        .unwrap();
+    assert_eq!(Json::default().serialize(&nested).unwrap(), deep.as_bytes());
+
+    // The stream readers honor the recursion limit too.
+    let deep_values = format!("{deep} {deep}");
+    assert!(Json::default()
+        .read_values::<Nested, _>(deep_values.as_bytes())
+        .all(|value| value.is_err()));
+    assert_eq!(
+        Json::default()
+            .disable_recursion_limit()
+            .read_values::<Nested, _>(deep_values.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .len(),
+        2
+    );
+    let deep_array = format!("[{deep}]");
+    assert!(Json::default()
+        .read_array::<Nested, _>(deep_array.as_bytes())
+        .all(|value| value.is_err()));
+    assert_eq!(
+        Json::default()
+            .disable_recursion_limit()
+            .read_array::<Nested, _>(deep_array.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn json_lines() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Event {
+        id: u32,
+        message: String,
+    }
+
+    let events = vec![
+        Event {
+            id: 1,
+            message: String::from("started"),
+        },
+        Event {
+            id: 2,
+            message: String::from("multi\nline"),
+        },
+    ];
+    let mut document = Vec::new();
+    JsonLines.write_lines(&events, &mut document).unwrap();
+    assert_eq!(
+        document,
+        b"{\"id\":1,\"message\":\"started\"}\n{\"id\":2,\"message\":\"multi\\nline\"}\n"
+    );
+
+    let read = JsonLines
+        .read_lines(&document[..])
+        .collect::<Result<Vec<Event>, _>>()
+        .unwrap();
+    assert_eq!(read, events);
+
+    // Blank lines, CRLF line endings, and a missing final newline are accepted.
+    let mut lines = JsonLines.read_lines::<u32, _>(&b"1\r\n\n2\nnope\n3"[..]);
+    assert_eq!(lines.next().unwrap().unwrap(), 1);
+    assert_eq!(lines.next().unwrap().unwrap(), 2);
+    assert!(matches!(lines.next(), Some(Err(Error::Json(_)))));
+    assert_eq!(lines.line_number(), 4);
+    assert_eq!(lines.next().unwrap().unwrap(), 3);
+    assert!(lines.next().is_none());
+}
+
+#[test]
+fn streams() {
+    let json = Json::default();
+
+    let mut values = json.read_values::<Vec<u32>, _>(&b"[1] [2, 3]\n[]"[..]);
+    assert_eq!(values.next().unwrap().unwrap(), vec![1]);
+    assert_eq!(values.byte_offset(), 3);
+    assert_eq!(values.next().unwrap().unwrap(), vec![2, 3]);
+    assert_eq!(values.next().unwrap().unwrap(), Vec::<u32>::new());
+    assert!(values.next().is_none());
+
+    let array = br#" [ "a,]", {"b": [1, {"c": "\"]"}]} ,null,  2.5 ] "#;
+    let elements = json
+        .read_array::<serde_json::Value, _>(&array[..])
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        elements,
+        serde_json::json!(["a,]", {"b": [1, {"c": "\"]"}]}, null, 2.5])
+            .as_array()
+            .unwrap()
+            .clone()
+    );
+    assert_eq!(json.read_array::<u32, _>(&b"[]"[..]).count(), 0);
+
+    for invalid in [&b"{}"[..], b"[1,]", b"[1", b"[1] 2", b"[1, x]"] {
+        assert!(
+            json.read_array::<u32, _>(invalid)
+                .any(|element| element.is_err()),
+            "{:?}",
+            std::str::from_utf8(invalid)
+        );
+    }
+}
+
+#[test]
+fn canonical() {
+    // The example from RFC 8785 §3.2.3.
+    let input = br#"{
+        "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+        "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+        "literals": [null, true, false]
+    }"#;
+    let expected = "{\"literals\":[null,true,false],\"numbers\":[333333333.3333333,1e+30,4.5,0.002,1e-27],\"string\":\"\u{20ac}$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}";
+
+    let value: serde_json::Value = CanonicalJson.deserialize_owned(input).unwrap();
+    let canonical = CanonicalJson.serialize(&value).unwrap();
+    assert_eq!(std::str::from_utf8(&canonical).unwrap(), expected);
+    CanonicalJson.verify(&canonical).unwrap();
+    assert!(matches!(
+        CanonicalJson.verify(input),
+        Err(Error::NotCanonical)
+    ));
+    assert!(matches!(CanonicalJson.verify(b"{"), Err(Error::Json(_))));
+
+    // Keys are sorted by UTF-16 code units, which places U+1F600 before U+FB33.
+    let keys = serde_json::json!({
+        "\u{20ac}": 0, "\r": 1, "\u{fb33}": 2, "1": 3, "\u{1f600}": 4, "\u{80}": 5, "\u{f6}": 6
+    });
+    assert_eq!(
+        std::str::from_utf8(&CanonicalJson.serialize(&keys).unwrap()).unwrap(),
+        "{\"\\r\":1,\"1\":3,\"\u{80}\":5,\"\u{f6}\":6,\"\u{20ac}\":0,\"\u{1f600}\":4,\"\u{fb33}\":2}"
+    );
+
+    assert!(matches!(
+        CanonicalJson.serialize(&(u64::MAX - 1)),
+        Err(Error::UnrepresentableNumber(_))
+    ));
+    // Large integers are written the way ECMAScript writes the equal double.
+    assert_eq!(
+        CanonicalJson.serialize(&(1_u64 << 60)).unwrap(),
+        b"1152921504606847000"
+    );
+}
+
+#[test]
+fn pointers() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct User<'a> {
+        name: &'a str,
+        roles: Vec<&'a str>,
+    }
+
+    let json = Json::default();
+    let document = br#"{
+        "users": [
+            {"name": "ecton", "roles": ["admin"]},
+            {"name": "daxpedda", "roles": [], "extra": {"ignored": [1, 2, 3]}}
+        ],
+        "a/b": {"m~n": 42},
+        "": "empty key"
+    }"#;
+
+    let user: User<'_> = json
+        .deserialize_pointer(document, "/users/1")
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        user,
+        User {
+            name: "daxpedda",
+            roles: vec![]
+        }
+    );
+    let name: &str = json
+        .deserialize_pointer(document, "/users/0/name")
+        .unwrap()
+        .unwrap();
+    assert_eq!(name, "ecton");
+    let escaped: u32 = json
+        .deserialize_pointer(document, "/a~1b/m~0n")
+        .unwrap()
+        .unwrap();
+    assert_eq!(escaped, 42);
+    let empty: String = json.deserialize_pointer(document, "/").unwrap().unwrap();
+    assert_eq!(empty, "empty key");
+    let whole: serde_json::Value = json.deserialize_pointer(document, "").unwrap().unwrap();
+    assert_eq!(whole["a/b"]["m~n"], 42);
+
+    for missing in [
+        "/users/2",
+        "/users/01",
+        "/users/-",
+        "/users/0/name/x",
+        "/nope",
+    ] {
+        assert!(json
+            .deserialize_pointer::<serde_json::Value>(document, missing)
+            .unwrap()
+            .is_none());
+    }
+    assert!(matches!(
+        json.deserialize_pointer::<u32>(document, "users"),
+        Err(Error::InvalidPointer(_))
+    ));
+    assert!(matches!(
+        json.deserialize_pointer::<u32>(document, "/a~2b"),
+        Err(Error::InvalidPointer(_))
+    ));
+    assert!(matches!(
+        json.deserialize_pointer::<u32>(br#"{"a": 1"#, "/a"),
+        Err(Error::Json(_))
+    ));
+}
+
+#[test]
+#[cfg(feature = "schema")]
+fn schema_validation() {
+    #[derive(Serialize, Deserialize, schemars::JsonSchema, Debug, PartialEq)]
+    struct Config {
+        name: String,
+        port: u16,
+    }
+
+    let json = Json::default().validate_schema_for::<Config>().unwrap();
+    let config: Config = json
+        .deserialize_from(&br#"{"name": "api", "port": 8080}"#[..])
+        .unwrap();
+    assert_eq!(config.port, 8080);
+
+    let Err(Error::SchemaValidation(violations)) =
+        OwnedDeserializer::<Config>::deserialize_owned(&json, br#"{"name": 1, "port": 70000}"#)
+    else {
+        unreachable!("invalid config deserialized")
+    };
+    let mut paths = violations
+        .iter()
+        .map(|violation| violation.instance_path.as_str())
+        .collect::<Vec<_>>();
+    paths.sort_unstable();
+    assert_eq!(paths, ["/name", "/port"]);
+
+    let json = Json::default()
+        .validate_schema(&serde_json::json!({"type": "array", "maxItems": 2}))
+        .unwrap();
+    let items: Vec<u8> = json.deserialize_borrowed(b"[1, 2]").unwrap();
+    assert_eq!(items, [1, 2]);
+    assert!(matches!(
+        OwnedDeserializer::<Vec<u8>>::deserialize_owned(&json, b"[1, 2, 3]"),
+        Err(Error::SchemaValidation(violations)) if violations.len() == 1 && violations[0].schema_path == "/maxItems"
+    ));
+
+    assert!(matches!(
+        Json::default().validate_schema(&serde_json::json!({"type": 5})),
+        Err(Error::InvalidSchema(_))
+    ));
+}
+
+#[test]
+fn sorted_keys() {
+    use std::collections::{BTreeMap, HashMap};
+
+    use serde_json::{json, value::RawValue, Value};
+
+    /// Serializes its entries as a map, in order, allowing duplicate keys.
+    struct Entries<K, V>(Vec<(K, V)>);
+
+    impl<K: Serialize, V: Serialize> Serialize for Entries<K, V> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+        }
+    }
+
+    #[derive(Serialize, PartialEq, Eq, PartialOrd, Ord)]
+    enum Key {
+        B,
+        A,
+    }
+
+    let sorted = Json::default().sort_keys();
+
+    // Keys that serialize to the same string are all kept, in their original
+    // order.
+    let duplicates = Entries(vec![
+        (json!("true"), 1),
+        (json!(1), 2),
+        (json!(true), 3),
+        (json!("1"), 4),
+    ]);
+    assert_eq!(
+        sorted.serialize(&duplicates).unwrap(),
+        br#"{"1":2,"1":4,"true":1,"true":3}"#
+    );
+
+    // Keys are converted to strings the same way `serde_json` converts them.
+    let keys = Entries(vec![(json!(2.5), 1), (json!(-1), 2), (json!(1.0), 3)]);
+    assert_eq!(
+        sorted.serialize(&keys).unwrap(),
+        br#"{"-1":2,"1.0":3,"2.5":1}"#
+    );
+    assert_eq!(
+        sorted
+            .serialize(&Entries(vec![(1.5_f32, 1), (0.5_f32, 2)]))
+            .unwrap(),
+        Json::default()
+            .serialize(&Entries(vec![(0.5_f32, 2), (1.5_f32, 1)]))
+            .unwrap()
+    );
+    assert_eq!(
+        sorted
+            .serialize(&HashMap::from([('b', 1), ('a', 2)]))
+            .unwrap(),
+        br#"{"a":2,"b":1}"#
+    );
+    assert_eq!(
+        sorted
+            .serialize(&BTreeMap::from([(Key::B, 1), (Key::A, 2)]))
+            .unwrap(),
+        br#"{"A":2,"B":1}"#
+    );
+    assert!(sorted
+        .serialize(&Entries(vec![(f64::NAN, 1)]))
+        .unwrap_err()
+        .to_string()
+        .contains("float key must be finite"));
+    assert!(sorted
+        .serialize(&Entries(vec![(Value::Null, 1)]))
+        .unwrap_err()
+        .to_string()
+        .contains("key must be a string"));
+
+    // Raw values are written as is, without sorting their contents.
+    let raw = RawValue::from_string(String::from(r#"{ "b": 1, "a": 2 }"#)).unwrap();
+    assert_eq!(
+        sorted.serialize(&HashMap::from([("raw", &raw)])).unwrap(),
+        br#"{"raw":{ "b": 1, "a": 2 }}"#
+    );
+}
@@ -0,0 +1,173 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+use crate::Error;
+
+/// Parses an [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer
+/// into its unescaped reference tokens.
+pub fn parse(pointer: &str) -> Result<Vec<String>, Error> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(tokens) = pointer.strip_prefix('/') else {
+        return Err(Error::InvalidPointer(pointer.to_string()));
+    };
+
+    tokens
+        .split('/')
+        .map(|token| {
+            let mut unescaped = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(ch) = chars.next() {
+                if ch == '~' {
+                    match chars.next() {
+                        Some('0') => unescaped.push('~'),
+                        Some('1') => unescaped.push('/'),
+                        _ => return Err(Error::InvalidPointer(pointer.to_string())),
+                    }
+                } else {
+                    unescaped.push(ch);
+                }
+            }
+            Ok(unescaped)
+        })
+        .collect()
+}
+
+/// Deserializes the value addressed by `tokens` as `T`, ignoring every other
+/// value. Produces `None` if the addressed value does not exist.
+pub struct PointerSeed<'a, T> {
+    tokens: &'a [String],
+    _value: PhantomData<T>,
+}
+
+impl<'a, T> PointerSeed<'a, T> {
+    pub fn new(tokens: &'a [String]) -> Self {
+        Self {
+            tokens,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for PointerSeed<'_, T>
+where
+    T: serde::Deserialize<'de>,
+{
+    type Value = Option<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        match self.tokens.split_first() {
+            None => T::deserialize(deserializer).map(Some),
+            Some((token, remaining)) => deserializer.deserialize_any(PointerVisitor {
+                token,
+                remaining,
+                _value: PhantomData,
+            }),
+        }
+    }
+}
+
+struct PointerVisitor<'a, T> {
+    token: &'a str,
+    remaining: &'a [String],
+    _value: PhantomData<T>,
+}
+
+impl<'de, T> Visitor<'de> for PointerVisitor<'_, T>
+where
+    T: serde::Deserialize<'de>,
+{
+    type Value = Option<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut found = None;
+        while let Some(matches) = map.next_key_seed(KeySeed(self.token))? {
+            if matches && found.is_none() {
+                found = map.next_value_seed(PointerSeed::new(self.remaining))?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(found)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // Array indexes are decimal without leading zeros. Any other token,
+        // including `-`, can't address an existing element.
+        let index = if self.token == "0" || !self.token.starts_with('0') {
+            self.token.parse::<usize>().ok()
+        } else {
+            None
+        };
+
+        let mut found = None;
+        let mut current = 0;
+        loop {
+            if Some(current) == index {
+                match seq.next_element_seed(PointerSeed::new(self.remaining))? {
+                    Some(value) => found = value,
+                    None => break,
+                }
+            } else if seq.next_element::<IgnoredAny>()?.is_none() {
+                break;
+            }
+            current += 1;
+        }
+        Ok(found)
+    }
+
+    // Scalars don't contain any values that can be addressed.
+
+    fn visit_bool<E: serde::de::Error>(self, _v: bool) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, _v: i64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, _v: u64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, _v: f64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, _v: &str) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+}
+
+/// Compares an object's key to a reference token without allocating.
+struct KeySeed<'a>(&'a str);
+
+impl<'de> DeserializeSeed<'de> for KeySeed<'_> {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for KeySeed<'_> {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an object key")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<bool, E> {
+        Ok(v == self.0)
+    }
+}
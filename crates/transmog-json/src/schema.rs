@@ -0,0 +1,52 @@
+use std::fmt::{self, Display};
+
+use jsonschema::Validator;
+use serde_json::Value;
+
+use crate::Error;
+
+/// A location where a value did not match a JSON Schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaViolation {
+    /// The JSON Pointer to the value that did not match the schema, such as
+    /// `/users/0/name`.
+    pub instance_path: String,
+    /// The JSON Pointer to the schema keyword that the value did not
+    /// satisfy, such as `/properties/users/items/required`.
+    pub schema_path: String,
+    /// A description of why the value did not match the schema.
+    pub message: String,
+}
+
+impl Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.instance_path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.instance_path, self.message)
+        }
+    }
+}
+
+/// Compiles `schema`, returning [`Error::InvalidSchema`] if it is not a valid
+/// JSON Schema.
+pub fn compile(schema: &Value) -> Result<Validator, Error> {
+    jsonschema::validator_for(schema).map_err(|err| Error::InvalidSchema(err.to_string()))
+}
+
+/// Validates `instance`, returning every violation of the schema.
+pub fn validate(validator: &Validator, instance: &Value) -> Result<(), Error> {
+    let violations = validator
+        .iter_errors(instance)
+        .map(|error| SchemaViolation {
+            instance_path: error.instance_path().to_string(),
+            schema_path: error.schema_path().to_string(),
+            message: error.to_string(),
+        })
+        .collect::<Vec<_>>();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::SchemaValidation(violations))
+    }
+}
@@ -0,0 +1,699 @@
+use std::io::{self, Write};
+
+use serde::{
+    ser::{
+        self, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+        SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize, Serializer,
+};
+use serde_json::{
+    ser::{CompactFormatter, Formatter, PrettyFormatter},
+    Error,
+};
+
+/// A [`Formatter`] that writes either compact or indented output, optionally
+/// escaping every non-ASCII character.
+pub struct JsonFormatter<'a> {
+    pretty: Option<PrettyFormatter<'a>>,
+    ascii_only: bool,
+}
+
+impl<'a> JsonFormatter<'a> {
+    pub fn new(indent: Option<&'a str>, ascii_only: bool) -> Self {
+        Self {
+            pretty: indent.map(|indent| PrettyFormatter::with_indent(indent.as_bytes())),
+            ascii_only,
+        }
+    }
+}
+
+impl Formatter for JsonFormatter<'_> {
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.begin_array(writer),
+            None => CompactFormatter.begin_array(writer),
+        }
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.end_array(writer),
+            None => CompactFormatter.end_array(writer),
+        }
+    }
+
+    fn begin_array_value<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.begin_array_value(writer, first),
+            None => CompactFormatter.begin_array_value(writer, first),
+        }
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.end_array_value(writer),
+            None => CompactFormatter.end_array_value(writer),
+        }
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.begin_object(writer),
+            None => CompactFormatter.begin_object(writer),
+        }
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.end_object(writer),
+            None => CompactFormatter.end_object(writer),
+        }
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.begin_object_key(writer, first),
+            None => CompactFormatter.begin_object_key(writer, first),
+        }
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.begin_object_value(writer),
+            None => CompactFormatter.begin_object_value(writer),
+        }
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match &mut self.pretty {
+            Some(pretty) => pretty.end_object_value(writer),
+            None => CompactFormatter.end_object_value(writer),
+        }
+    }
+
+    fn write_string_fragment<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        fragment: &str,
+    ) -> io::Result<()> {
+        if !self.ascii_only || fragment.is_ascii() {
+            return writer.write_all(fragment.as_bytes());
+        }
+
+        for ch in fragment.chars() {
+            if ch.is_ascii() {
+                writer.write_all(&[ch as u8])?;
+            } else {
+                // Characters outside of the Basic Multilingual Plane are
+                // escaped as a UTF-16 surrogate pair.
+                for unit in ch.encode_utf16(&mut [0; 2]) {
+                    write!(writer, "\\u{unit:04x}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The names `serde_json` uses to serialize a `RawValue` and, with the
+/// `arbitrary_precision` feature, a `Number`. Both are serialized as a struct
+/// whose only field has the same name, and whose value is written verbatim.
+const VERBATIM_TOKENS: [&str; 2] = [
+    "$serde_json::private::RawValue",
+    "$serde_json::private::Number",
+];
+
+/// A serialized value whose objects' entries are ordered by key.
+///
+/// Unlike [`serde_json::Value`], integers are stored using 128 bits, objects
+/// keep entries with duplicate keys, and values that `serde_json` writes
+/// verbatim are kept as is, so sorting only changes the order of entries.
+pub enum Sorted {
+    Null,
+    Bool(bool),
+    Unsigned(u128),
+    Signed(i128),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Array(Vec<Sorted>),
+    Object(Vec<(String, Sorted)>),
+    Verbatim { token: &'static str, value: String },
+}
+
+impl Sorted {
+    pub fn new<T: Serialize + ?Sized>(value: &T) -> Result<Self, Error> {
+        value.serialize(SortedSerializer)
+    }
+}
+
+impl Serialize for Sorted {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Sorted::Null => serializer.serialize_unit(),
+            Sorted::Bool(value) => serializer.serialize_bool(*value),
+            Sorted::Unsigned(value) => serializer.serialize_u128(*value),
+            Sorted::Signed(value) => serializer.serialize_i128(*value),
+            Sorted::F32(value) => serializer.serialize_f32(*value),
+            Sorted::F64(value) => serializer.serialize_f64(*value),
+            Sorted::String(value) => serializer.serialize_str(value),
+            Sorted::Array(values) => serializer.collect_seq(values),
+            Sorted::Object(entries) => {
+                serializer.collect_map(entries.iter().map(|(key, value)| (key, value)))
+            }
+            Sorted::Verbatim { token, value } => {
+                let mut verbatim = serializer.serialize_struct(token, 1)?;
+                verbatim.serialize_field(token, value)?;
+                verbatim.end()
+            }
+        }
+    }
+}
+
+/// Converts a value into a [`Sorted`], representing values the same way
+/// `serde_json` does.
+struct SortedSerializer;
+
+impl Serializer for SortedSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = VariantSerializer<SeqSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = VariantSerializer<MapSerializer>;
+
+    fn serialize_bool(self, v: bool) -> Result<Sorted, Error> {
+        Ok(Sorted::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Sorted, Error> {
+        self.serialize_i128(i128::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Sorted, Error> {
+        self.serialize_i128(i128::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Sorted, Error> {
+        self.serialize_i128(i128::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Sorted, Error> {
+        self.serialize_i128(i128::from(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Sorted, Error> {
+        Ok(Sorted::Signed(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Sorted, Error> {
+        self.serialize_u128(u128::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Sorted, Error> {
+        self.serialize_u128(u128::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Sorted, Error> {
+        self.serialize_u128(u128::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Sorted, Error> {
+        self.serialize_u128(u128::from(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Sorted, Error> {
+        Ok(Sorted::Unsigned(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Sorted, Error> {
+        Ok(Sorted::F32(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Sorted, Error> {
+        Ok(Sorted::F64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Sorted, Error> {
+        Ok(Sorted::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Sorted, Error> {
+        Ok(Sorted::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Sorted, Error> {
+        Ok(Sorted::Array(
+            v.iter()
+                .map(|byte| Sorted::Unsigned(u128::from(*byte)))
+                .collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Sorted, Error> {
+        Ok(Sorted::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Sorted, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Sorted, Error> {
+        Ok(Sorted::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Sorted, Error> {
+        Ok(Sorted::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Sorted, Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Sorted, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Sorted, Error> {
+        Ok(wrap_variant(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<SeqSerializer>, Error> {
+        Ok(VariantSerializer {
+            variant,
+            contents: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer, Error> {
+        Ok(MapSerializer {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+            verbatim: None,
+        })
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<MapSerializer, Error> {
+        let mut map = self.serialize_map(Some(len))?;
+        map.verbatim = VERBATIM_TOKENS.into_iter().find(|token| *token == name);
+        Ok(map)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<MapSerializer>, Error> {
+        Ok(VariantSerializer {
+            variant,
+            contents: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+pub struct SeqSerializer(Vec<Sorted>);
+
+impl SerializeSeq for SeqSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.0.push(value.serialize(SortedSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Sorted, Error> {
+        Ok(Sorted::Array(self.0))
+    }
+}
+
+impl SerializeTuple for SeqSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Sorted, Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for SeqSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Sorted, Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+pub struct MapSerializer {
+    entries: Vec<(String, Sorted)>,
+    key: Option<String>,
+    /// The token of the `serde_json` value being serialized verbatim, if any.
+    verbatim: Option<&'static str>,
+}
+
+impl SerializeMap for MapSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <Error as ser::Error>::custom("value serialized before its key"))?;
+        self.entries.push((key, value.serialize(SortedSerializer)?));
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<Sorted, Error> {
+        if let Some(token) = self.verbatim {
+            return match self.entries.pop() {
+                Some((_, Sorted::String(value))) if self.entries.is_empty() => {
+                    Ok(Sorted::Verbatim { token, value })
+                }
+                _ => Err(ser::Error::custom(format!("invalid {token}"))),
+            };
+        }
+
+        // The sort is stable, so entries with duplicate keys keep their order.
+        self.entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(Sorted::Object(self.entries))
+    }
+}
+
+impl SerializeStruct for MapSerializer {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.entries
+            .push((key.to_string(), value.serialize(SortedSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Sorted, Error> {
+        SerializeMap::end(self)
+    }
+}
+
+/// Serializes an enum variant's contents, which are wrapped in an object
+/// whose only key is the variant's name.
+pub struct VariantSerializer<S> {
+    variant: &'static str,
+    contents: S,
+}
+
+fn wrap_variant(variant: &'static str, contents: Sorted) -> Sorted {
+    Sorted::Object(vec![(variant.to_string(), contents)])
+}
+
+impl SerializeTupleVariant for VariantSerializer<SeqSerializer> {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        SerializeSeq::serialize_element(&mut self.contents, value)
+    }
+
+    fn end(self) -> Result<Sorted, Error> {
+        Ok(wrap_variant(
+            self.variant,
+            SerializeSeq::end(self.contents)?,
+        ))
+    }
+}
+
+impl SerializeStructVariant for VariantSerializer<MapSerializer> {
+    type Ok = Sorted;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        SerializeStruct::serialize_field(&mut self.contents, key, value)
+    }
+
+    fn end(self) -> Result<Sorted, Error> {
+        Ok(wrap_variant(
+            self.variant,
+            SerializeMap::end(self.contents)?,
+        ))
+    }
+}
+
+/// Converts a map key into the string `serde_json` writes for it, accepting
+/// the same keys as `serde_json`.
+struct KeySerializer;
+
+fn key_must_be_a_string() -> Error {
+    ser::Error::custom("key must be a string")
+}
+
+impl KeySerializer {
+    fn float_key<F: Serialize>(value: F, is_finite: bool) -> Result<String, Error> {
+        if is_finite {
+            serde_json::to_string(&value)
+        } else {
+            Err(ser::Error::custom(
+                "float key must be finite (got NaN or +/-inf)",
+            ))
+        }
+    }
+}
+
+impl Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<String, Error> {
+        Self::float_key(v, v.is_finite())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<String, Error> {
+        Self::float_key(v, v.is_finite())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(key_must_be_a_string())
+    }
+}
@@ -0,0 +1,329 @@
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read},
+    marker::PhantomData,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use serde_json::{de::IoRead, StreamDeserializer};
+
+use crate::{Error, Json};
+
+/// An iterator over the values of a JSON Lines document. Returned from
+/// [`JsonLines::read_lines`](crate::JsonLines::read_lines).
+#[derive(Debug)]
+pub struct LineReader<R, T> {
+    reader: BufReader<R>,
+    line: String,
+    line_number: u64,
+    _value: PhantomData<T>,
+}
+
+impl<R, T> LineReader<R, T>
+where
+    R: Read,
+{
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            line: String::new(),
+            line_number: 0,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<R, T> LineReader<R, T> {
+    /// Returns the one-based number of the line most recently read.
+    #[must_use]
+    pub fn line_number(&self) -> u64 {
+        self.line_number
+    }
+}
+
+impl<R, T> Iterator for LineReader<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(err) => return Some(Err(Error::from(err))),
+            }
+            self.line_number += 1;
+
+            let line = self.line.trim_end_matches(['\n', '\r']);
+            if !line.trim().is_empty() {
+                return Some(serde_json::from_str(line).map_err(Error::from));
+            }
+        }
+    }
+}
+
+/// An iterator over a sequence of whitespace-separated JSON values. Returned
+/// from [`Json::read_values`](crate::Json::read_values).
+pub struct ValueReader<R, T>
+where
+    R: Read,
+{
+    values: Values<R, T>,
+}
+
+enum Values<R, T>
+where
+    R: Read,
+{
+    Bounded(StreamDeserializer<'static, IoRead<R>, T>),
+    Unbounded(StreamDeserializer<'static, IoRead<R>, Unbounded<T>>),
+}
+
+/// Deserializes `T` using [`serde_stacker`], which grows the stack as needed
+/// when the recursion limit is disabled.
+struct Unbounded<T>(T);
+
+impl<'de, T> Deserialize<'de> for Unbounded<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(serde_stacker::Deserializer::new(deserializer)).map(Self)
+    }
+}
+
+impl<R, T> ValueReader<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    pub(crate) fn new(reader: R, unbounded_depth: bool) -> Self {
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        let values = if unbounded_depth {
+            deserializer.disable_recursion_limit();
+            Values::Unbounded(deserializer.into_iter())
+        } else {
+            Values::Bounded(deserializer.into_iter())
+        };
+        Self { values }
+    }
+
+    /// Returns the number of bytes consumed by the values read so far.
+    #[must_use]
+    pub fn byte_offset(&self) -> usize {
+        match &self.values {
+            Values::Bounded(values) => values.byte_offset(),
+            Values::Unbounded(values) => values.byte_offset(),
+        }
+    }
+}
+
+impl<R, T> fmt::Debug for ValueReader<R, T>
+where
+    R: Read,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueReader")
+            .field(
+                "unbounded_depth",
+                &matches!(self.values, Values::Unbounded(_)),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, T> Iterator for ValueReader<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = match &mut self.values {
+            Values::Bounded(values) => values.next()?,
+            Values::Unbounded(values) => values.next()?.map(|Unbounded(value)| value),
+        };
+        Some(value.map_err(Error::from))
+    }
+}
+
+/// An iterator over the elements of a top-level JSON array. Returned from
+/// [`Json::read_array`](crate::Json::read_array).
+///
+/// Only one element is buffered at a time, regardless of the length of the
+/// array.
+#[derive(Debug)]
+pub struct ArrayReader<R, T> {
+    bytes: io::Bytes<BufReader<R>>,
+    peeked: Option<u8>,
+    byte_offset: usize,
+    element: Vec<u8>,
+    state: ArrayState,
+    json: Json,
+    _value: PhantomData<T>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum ArrayState {
+    /// The opening `[` has not been read.
+    Start,
+    /// The array is open, and the next element has not been read.
+    Elements,
+    /// The closing `]` has been read, but the remaining input has not been
+    /// checked.
+    Closed,
+    /// Iteration has finished.
+    Done,
+}
+
+impl<R, T> ArrayReader<R, T>
+where
+    R: Read,
+{
+    pub(crate) fn new(reader: R, json: Json) -> Self {
+        Self {
+            bytes: BufReader::new(reader).bytes(),
+            peeked: None,
+            byte_offset: 0,
+            element: Vec::new(),
+            state: ArrayState::Start,
+            json,
+            _value: PhantomData,
+        }
+    }
+
+    /// Returns the number of bytes consumed from the reader so far.
+    #[must_use]
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    fn next_byte(&mut self) -> Result<Option<u8>, Error> {
+        if let Some(byte) = self.peeked.take() {
+            return Ok(Some(byte));
+        }
+        match self.bytes.next().transpose()? {
+            Some(byte) => {
+                self.byte_offset += 1;
+                Ok(Some(byte))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_non_whitespace(&mut self) -> Result<Option<u8>, Error> {
+        while let Some(byte) = self.next_byte()? {
+            if !byte.is_ascii_whitespace() {
+                return Ok(Some(byte));
+            }
+        }
+        Ok(None)
+    }
+
+    fn syntax_error(&self, message: &str) -> Error {
+        Error::Json(serde::de::Error::custom(format_args!(
+            "{message} at byte offset {}",
+            self.byte_offset
+        )))
+    }
+
+    /// Reads the next element into `self.element`, returning false once the
+    /// array has been fully read.
+    fn read_element(&mut self) -> Result<bool, Error> {
+        match self.state {
+            ArrayState::Start => {
+                if self.next_non_whitespace()? != Some(b'[') {
+                    return Err(self.syntax_error("expected `[`"));
+                }
+                match self.next_non_whitespace()? {
+                    Some(b']') => return self.close(),
+                    first => self.peeked = first,
+                }
+            }
+            ArrayState::Elements => {}
+            ArrayState::Closed => return self.close(),
+            ArrayState::Done => return Ok(false),
+        }
+        self.state = ArrayState::Elements;
+
+        match self.next_non_whitespace()? {
+            Some(b',' | b']') => return Err(self.syntax_error("expected value")),
+            Some(first) => self.peeked = Some(first),
+            None => return Err(Error::from(io::Error::from(io::ErrorKind::UnexpectedEof))),
+        }
+
+        // Find the end of the element by tracking strings and nesting, leaving
+        // the element itself to be validated when it is deserialized.
+        self.element.clear();
+        let mut depth = 0_usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        loop {
+            let byte = self
+                .next_byte()?
+                .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if byte == b'\\' {
+                    escaped = true;
+                } else if byte == b'"' {
+                    in_string = false;
+                }
+            } else {
+                match byte {
+                    b',' if depth == 0 => return Ok(true),
+                    b']' if depth == 0 => {
+                        self.state = ArrayState::Closed;
+                        return Ok(true);
+                    }
+                    b'"' => in_string = true,
+                    b'[' | b'{' => depth += 1,
+                    b']' | b'}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            self.element.push(byte);
+        }
+    }
+
+    /// Ensures that only whitespace follows the closing `]`.
+    fn close(&mut self) -> Result<bool, Error> {
+        self.state = ArrayState::Done;
+        if self.next_non_whitespace()?.is_some() {
+            Err(self.syntax_error("trailing characters"))
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+impl<R, T> Iterator for ArrayReader<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_element() {
+            Ok(true) => Some(
+                self.json
+                    .deserialize(serde_json::Deserializer::from_slice(&self.element)),
+            ),
+            Ok(false) => None,
+            Err(err) => {
+                self.state = ArrayState::Done;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+mod de;
+mod parse;
+
+use std::io::{Read, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+pub use serde_json;
+pub use transmog;
+use transmog::{Format, OwnedDeserializer};
+
+/// JSON5 implementor of [`Format`].
+///
+/// Deserialization accepts [JSON5](https://json5.org/), which is a superset
+/// of JSON that allows comments, trailing commas, unquoted keys,
+/// single-quoted strings, hexadecimal numbers, `Infinity`, `NaN`, and more.
+/// JSONC (JSON with comments) is a subset of JSON5, so it is accepted too.
+/// Integers are range checked against the requested type, including integers
+/// that require 128 bits.
+///
+/// Serialization always produces strict JSON, which every JSON5 parser can
+/// read.
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct Json5 {
+    pretty: bool,
+}
+
+impl Json5 {
+    /// Returns an instance configured to serialize in a "pretty" format.
+    pub fn pretty(mut self) -> Self {
+        self.pretty = true;
+        self
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(data: &str) -> Result<T, Error> {
+    T::deserialize(parse::parse(data)?).map_err(Error::from)
+}
+
+impl<T> Format<'_, T> for Json5
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        let mut bytes = Vec::with_capacity(128);
+        self.serialize_into(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, writer: W) -> Result<(), Self::Error> {
+        if self.pretty {
+            serde_json::to_writer_pretty(writer, value)?;
+        } else {
+            serde_json::to_writer(writer, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Json5
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        deserialize(std::str::from_utf8(data)?)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+        deserialize(&data)
+    }
+}
+
+/// Errors from [`Json5`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The input was not valid JSON5.
+    #[error("json5 error at line {line}, column {column}: {message}")]
+    Json5 {
+        /// A description of the problem.
+        message: &'static str,
+        /// The one-based line of the problem.
+        line: usize,
+        /// The one-based column of the problem, in characters.
+        column: usize,
+    },
+    /// An error occurred serializing a value, or the input's value could not
+    /// be deserialized as the requested type.
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The input was not valid UTF-8.
+    #[error("invalid utf-8: {0}")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+    /// An Io error occurred outside of `Json5`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Json5::default());
+    transmog::test_util::test_format(&Json5::default().pretty());
+}
+
+#[test]
+fn lenient_syntax() {
+    #[derive(Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Config {
+        name: String,
+        port: u16,
+        hosts: Vec<String>,
+        mask: u32,
+    }
+
+    let config: Config = Json5::default()
+        .deserialize_owned(
+            br#"
+            // Edited by hand.
+            {
+                name: 'api', /* unquoted key, single quotes */
+                "port": 8080,
+                hosts: [
+                    "a.example",
+                    "b.example", // trailing comma
+                ],
+                mask: 0xFF,
+            }
+            "#,
+        )
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: String::from("api"),
+            port: 8080,
+            hosts: vec![String::from("a.example"), String::from("b.example")],
+            mask: 255,
+        }
+    );
+
+    // Serialization is strict JSON.
+    assert_eq!(
+        Json5::default().serialize(&config).unwrap(),
+        br#"{"name":"api","port":8080,"hosts":["a.example","b.example"],"mask":255}"#
+    );
+
+    // Out of range integers are rejected rather than truncated.
+    assert!(matches!(
+        OwnedDeserializer::<u8>::deserialize_owned(&Json5::default(), b"300"),
+        Err(Error::Json(_))
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<u8>::deserialize_owned(&Json5::default(), b"{"),
+        Err(Error::Json5 {
+            line: 1,
+            column: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn numbers() {
+    use std::collections::BTreeMap;
+
+    fn round_trip<T>(value: &T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let serialized = Json5::default().serialize(value).unwrap();
+        let deserialized: T = Json5::default().deserialize_owned(&serialized).unwrap();
+        assert_eq!(&deserialized, value);
+    }
+
+    round_trip(&u64::MAX);
+    round_trip(&i64::MIN);
+    round_trip(&u128::MAX);
+    round_trip(&i128::MIN);
+    round_trip(&-0.5_f64);
+    round_trip(&BTreeMap::from([(u64::MAX, true), (0, false)]));
+    round_trip(&BTreeMap::from([(i128::MIN, 1.5_f32)]));
+
+    let floats: Vec<f64> = Json5::default()
+        .deserialize_owned(b"[Infinity, -Infinity, +Infinity, NaN, -NaN, .5, 5., 1e3, 0x10]")
+        .unwrap();
+    assert_eq!(
+        floats[..3],
+        [f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY]
+    );
+    assert!(floats[3].is_nan() && floats[4].is_nan());
+    assert_eq!(floats[5..], [0.5, 5., 1000., 16.]);
+    let optional: Option<f64> = Json5::default().deserialize_owned(b"NaN").unwrap();
+    assert!(optional.unwrap().is_nan());
+
+    let hex: (u64, i64) = Json5::default()
+        .deserialize_owned(b"[0xFFFFFFFFFFFFFFFF, -0x8000000000000000]")
+        .unwrap();
+    assert_eq!(hex, (u64::MAX, i64::MIN));
+
+    for (invalid, json) in [
+        (&b"18446744073709551616"[..], true),
+        (b"-1", true),
+        (b"1.5", true),
+        (b"Infinity", true),
+        (b"01", false),
+        (b"0x", false),
+        (b"1e", false),
+        (b"-", false),
+    ] {
+        let result = OwnedDeserializer::<u64>::deserialize_owned(&Json5::default(), invalid);
+        assert_eq!(
+            matches!(result, Err(Error::Json(_))),
+            json,
+            "{:?}",
+            std::str::from_utf8(invalid)
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn strings() {
+    use std::collections::BTreeMap;
+
+    let strings: BTreeMap<String, String> = Json5::default()
+        .deserialize_owned(
+            "{ $id: '\\x41\\u00e9\\ud83d\\ude00', _a1: \"it's \\\"quoted\\\"\", \
+             'line': 'one\\\ntwo\\n', \u{e9}t\u{e9}: '\\0\\q' }"
+                .as_bytes(),
+        )
+        .unwrap();
+    assert_eq!(
+        strings,
+        BTreeMap::from([
+            (String::from("$id"), String::from("A\u{e9}\u{1f600}")),
+            (String::from("_a1"), String::from("it's \"quoted\"")),
+            (String::from("line"), String::from("onetwo\n")),
+            (String::from("\u{e9}t\u{e9}"), String::from("\0q")),
+        ])
+    );
+
+    for (invalid, line, column) in [
+        (&b"'unterminated"[..], 1, 14),
+        (b"\n  [1 2]", 2, 6),
+        (b"{1: true}", 1, 2),
+        (b"'\\1'", 1, 4),
+        (b"/* open", 1, 3),
+        (b"[] []", 1, 4),
+    ] {
+        assert!(
+            matches!(
+                OwnedDeserializer::<serde_json::Value>::deserialize_owned(&Json5::default(), invalid),
+                Err(Error::Json5 { line: l, column: c, .. }) if (l, c) == (line, column)
+            ),
+            "{:?}",
+            std::str::from_utf8(invalid)
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use crate::Error;
+
+/// The maximum depth of nested arrays and objects, which matches
+/// `serde_json`'s recursion limit.
+const RECURSION_LIMIT: usize = 128;
+
+/// A parsed JSON5 value.
+///
+/// Numbers are kept exactly as written when possible: integers are stored
+/// using 128 bits, and only numbers with a fraction or an exponent, numbers
+/// too large for 128 bits, `Infinity`, and `NaN` are stored as floats.
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Unsigned(u128),
+    Negative(i128),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+/// Parses `input` as a single [JSON5](https://spec.json5.org/) value.
+pub fn parse(input: &str) -> Result<Value, Error> {
+    let mut parser = Parser { input, offset: 0 };
+    parser.skip_whitespace()?;
+    let value = parser.value(RECURSION_LIMIT)?;
+    parser.skip_whitespace()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &'static str) -> Error {
+        let before = &self.input[..self.offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Error::Json5 {
+            message,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    fn rest(&self) -> &str {
+        &self.input[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.offset += ch.len_utf8();
+        Some(ch)
+    }
+
+    /// Consumes `expected` if the input continues with it.
+    fn eat(&mut self, expected: &str) -> bool {
+        if self.rest().starts_with(expected) {
+            self.offset += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_whitespace(&mut self) -> Result<(), Error> {
+        loop {
+            match self.peek() {
+                Some(ch) if ch.is_whitespace() || ch == '\u{feff}' => {
+                    self.next();
+                }
+                Some('/') if self.eat("//") => {
+                    while self.peek().is_some_and(|ch| !is_line_terminator(ch)) {
+                        self.next();
+                    }
+                }
+                Some('/') if self.eat("/*") => match self.rest().find("*/") {
+                    Some(end) => self.offset += end + 2,
+                    None => return Err(self.error("unterminated comment")),
+                },
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn value(&mut self, recursion_limit: usize) -> Result<Value, Error> {
+        match self.peek() {
+            Some('{') => self.object(recursion_limit),
+            Some('[') => self.array(recursion_limit),
+            Some(quote @ ('"' | '\'')) => {
+                self.next();
+                self.string(quote).map(Value::String)
+            }
+            Some(_) if self.eat("null") => Ok(Value::Null),
+            Some(_) if self.eat("true") => Ok(Value::Bool(true)),
+            Some(_) if self.eat("false") => Ok(Value::Bool(false)),
+            Some('0'..='9' | '+' | '-' | '.' | 'I' | 'N') => self.number(),
+            Some(_) => Err(self.error("expected value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn nested(&self, recursion_limit: usize) -> Result<usize, Error> {
+        recursion_limit
+            .checked_sub(1)
+            .ok_or_else(|| self.error("recursion limit exceeded"))
+    }
+
+    fn array(&mut self, recursion_limit: usize) -> Result<Value, Error> {
+        let recursion_limit = self.nested(recursion_limit)?;
+        self.next();
+        let mut values = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            if self.eat("]") {
+                return Ok(Value::Array(values));
+            }
+            values.push(self.value(recursion_limit)?);
+            self.skip_whitespace()?;
+            if !self.eat(",") {
+                return if self.eat("]") {
+                    Ok(Value::Array(values))
+                } else {
+                    Err(self.error("expected `,` or `]`"))
+                };
+            }
+        }
+    }
+
+    fn object(&mut self, recursion_limit: usize) -> Result<Value, Error> {
+        let recursion_limit = self.nested(recursion_limit)?;
+        self.next();
+        let mut entries = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            if self.eat("}") {
+                return Ok(Value::Object(entries));
+            }
+            let key = match self.peek() {
+                Some(quote @ ('"' | '\'')) => {
+                    self.next();
+                    self.string(quote)?
+                }
+                _ => self.identifier()?,
+            };
+            self.skip_whitespace()?;
+            if !self.eat(":") {
+                return Err(self.error("expected `:`"));
+            }
+            self.skip_whitespace()?;
+            entries.push((key, self.value(recursion_limit)?));
+            self.skip_whitespace()?;
+            if !self.eat(",") {
+                return if self.eat("}") {
+                    Ok(Value::Object(entries))
+                } else {
+                    Err(self.error("expected `,` or `}`"))
+                };
+            }
+        }
+    }
+
+    /// Parses an unquoted object key, which is an ECMAScript identifier name.
+    fn identifier(&mut self) -> Result<String, Error> {
+        let mut identifier = String::new();
+        loop {
+            let start = self.offset;
+            let ch = match self.next() {
+                Some('\\') => {
+                    if !self.eat("u") {
+                        return Err(self.error("invalid escape in key"));
+                    }
+                    self.unicode_escape()?
+                }
+                Some(ch) => ch,
+                None => break,
+            };
+            let valid = ch == '$'
+                || ch == '_'
+                || if identifier.is_empty() {
+                    ch.is_alphabetic()
+                } else {
+                    ch.is_alphanumeric() || ch == '\u{200c}' || ch == '\u{200d}'
+                };
+            if !valid {
+                self.offset = start;
+                break;
+            }
+            identifier.push(ch);
+        }
+
+        if identifier.is_empty() {
+            Err(self.error("expected key"))
+        } else {
+            Ok(identifier)
+        }
+    }
+
+    /// Parses the rest of a string that was opened with `quote`.
+    fn string(&mut self, quote: char) -> Result<String, Error> {
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some(ch) if ch == quote => return Ok(string),
+                Some('\\') => {
+                    if let Some(ch) = self.escape()? {
+                        string.push(ch);
+                    }
+                }
+                Some('\n' | '\r') | None => return Err(self.error("unterminated string")),
+                Some(ch) => string.push(ch),
+            }
+        }
+    }
+
+    /// Parses the escape sequence following a `\`, returning `None` for an
+    /// escaped line terminator, which continues the string on the next line.
+    fn escape(&mut self) -> Result<Option<char>, Error> {
+        let ch = match self.next() {
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('v') => '\u{b}',
+            Some('0') if !self.peek().is_some_and(|ch| ch.is_ascii_digit()) => '\0',
+            Some('x') => {
+                let code = self.hex_digits(2)?;
+                char::from_u32(code).expect("two hex digits are a valid char")
+            }
+            Some('u') => self.unicode_escape()?,
+            Some('\r') => {
+                self.eat("\n");
+                return Ok(None);
+            }
+            Some(ch) if is_line_terminator(ch) => return Ok(None),
+            Some('0'..='9') => return Err(self.error("invalid escape")),
+            Some(ch) => ch,
+            None => return Err(self.error("unterminated string")),
+        };
+        Ok(Some(ch))
+    }
+
+    /// Parses the four hex digits following `\u`, combining surrogate pairs.
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let high = self.hex_digits(4)?;
+        let code = if (0xd800..0xdc00).contains(&high) && self.eat("\\u") {
+            let low = self.hex_digits(4)?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("invalid surrogate pair"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex_digits(&mut self, count: usize) -> Result<u32, Error> {
+        let digits = self
+            .rest()
+            .get(..count)
+            .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid hex escape"))?;
+        let value = u32::from_str_radix(digits, 16).expect("validated hex digits");
+        self.offset += count;
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<Value, Error> {
+        let start = self.offset;
+        let negative = self.eat("-");
+        if !negative {
+            self.eat("+");
+        }
+
+        if self.eat("Infinity") {
+            return Ok(Value::Float(if negative {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            }));
+        } else if self.eat("NaN") {
+            return Ok(Value::Float(f64::NAN));
+        } else if self.eat("0x") || self.eat("0X") {
+            let digits_start = self.offset;
+            self.skip_while(|byte| byte.is_ascii_hexdigit());
+            let digits = &self.input[digits_start..self.offset];
+            if digits.is_empty() {
+                return Err(self.error("expected hex digits"));
+            }
+            if let Ok(magnitude) = u128::from_str_radix(digits, 16) {
+                return Ok(integer(negative, magnitude));
+            }
+            let magnitude = digits.bytes().fold(0_f64, |value, digit| {
+                value * 16. + f64::from(char::from(digit).to_digit(16).unwrap_or(0))
+            });
+            return Ok(Value::Float(if negative { -magnitude } else { magnitude }));
+        }
+
+        let integer_start = self.offset;
+        self.skip_while(|byte| byte.is_ascii_digit());
+        let integer_digits = &self.input[integer_start..self.offset];
+        if integer_digits.len() > 1 && integer_digits.starts_with('0') {
+            return Err(self.error("leading zeros are not allowed"));
+        }
+        let mut is_integer = true;
+        let mut has_digits = !integer_digits.is_empty();
+        if self.eat(".") {
+            is_integer = false;
+            let fraction_start = self.offset;
+            self.skip_while(|byte| byte.is_ascii_digit());
+            has_digits |= self.offset > fraction_start;
+        }
+        if !has_digits {
+            return Err(self.error("expected digits"));
+        }
+        if self.eat("e") || self.eat("E") {
+            is_integer = false;
+            if !self.eat("+") {
+                self.eat("-");
+            }
+            let exponent_start = self.offset;
+            self.skip_while(|byte| byte.is_ascii_digit());
+            if self.offset == exponent_start {
+                return Err(self.error("expected exponent digits"));
+            }
+        }
+
+        let text = &self.input[start..self.offset];
+        if is_integer {
+            if let Ok(magnitude) = integer_digits.parse::<u128>() {
+                return Ok(integer(negative, magnitude));
+            }
+        }
+        text.parse()
+            .map(Value::Float)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    fn skip_while(&mut self, mut predicate: impl FnMut(u8) -> bool) {
+        let len = self
+            .rest()
+            .bytes()
+            .take_while(|byte| predicate(*byte))
+            .count();
+        self.offset += len;
+    }
+}
+
+/// Returns the integer with `magnitude`, falling back to a float when it is
+/// too small for an `i128`.
+#[allow(clippy::cast_precision_loss)]
+fn integer(negative: bool, magnitude: u128) -> Value {
+    if !negative {
+        Value::Unsigned(magnitude)
+    } else if magnitude == 0 {
+        // Like `serde_json`, `-0` is a float.
+        Value::Float(-0.)
+    } else {
+        0_i128
+            .checked_sub_unsigned(magnitude)
+            .map_or(Value::Float(-(magnitude as f64)), Value::Negative)
+    }
+}
+
+fn is_line_terminator(ch: char) -> bool {
+    matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
@@ -0,0 +1,372 @@
+use std::borrow::Cow;
+
+use serde::de::{
+    DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+
+use crate::{Error, ELEMENT, END};
+
+/// Reads values written by [`Serializer`](crate::ser::Serializer).
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+    descending: bool,
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn new(input: &'de [u8], descending: bool) -> Self {
+        Self { input, descending }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    fn read_byte(&mut self) -> Result<u8, Error> {
+        let [byte] = self.read_array::<1>()?;
+        Ok(byte)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        if self.input.len() < N {
+            return Err(Error::UnexpectedEof);
+        }
+        let (bytes, remaining) = self.input.split_at(N);
+        self.input = remaining;
+
+        let mut array = [0; N];
+        array.copy_from_slice(bytes);
+        if self.descending {
+            for byte in &mut array {
+                *byte = !*byte;
+            }
+        }
+        Ok(array)
+    }
+
+    /// Reads a value written by `write_escaped`, borrowing it from the input
+    /// when it is stored verbatim: in ascending order, with no escaped zero
+    /// bytes.
+    fn read_escaped(&mut self) -> Result<Cow<'de, [u8]>, Error> {
+        if !self.descending {
+            if let Some(end) = self.input.iter().position(|&byte| byte == 0) {
+                if self.input.get(end + 1) == Some(&0) {
+                    let bytes = &self.input[..end];
+                    self.input = &self.input[end + 2..];
+                    return Ok(Cow::Borrowed(bytes));
+                }
+            }
+        }
+
+        let mut bytes = Vec::new();
+        loop {
+            match self.read_byte()? {
+                0 => match self.read_byte()? {
+                    0 => return Ok(Cow::Owned(bytes)),
+                    0xFF => bytes.push(0),
+                    _ => return Err(Error::InvalidData("invalid escape sequence")),
+                },
+                byte => bytes.push(byte),
+            }
+        }
+    }
+
+    fn read_marker(&mut self) -> Result<bool, Error> {
+        match self.read_byte()? {
+            ELEMENT => Ok(true),
+            END => Ok(false),
+            _ => Err(Error::InvalidData("invalid collection marker")),
+        }
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error::NotSelfDescribing)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.read_byte()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            _ => Err(Error::InvalidData("invalid bool")),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i8(i8::from_be_bytes(self.read_array()?) ^ i8::MIN)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i16(i16::from_be_bytes(self.read_array()?) ^ i16::MIN)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i32(i32::from_be_bytes(self.read_array()?) ^ i32::MIN)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i64(i64::from_be_bytes(self.read_array()?) ^ i64::MIN)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i128(i128::from_be_bytes(self.read_array()?) ^ i128::MIN)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u8(self.read_byte()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u16(u16::from_be_bytes(self.read_array()?))
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u32(u32::from_be_bytes(self.read_array()?))
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u64(u64::from_be_bytes(self.read_array()?))
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u128(u128::from_be_bytes(self.read_array()?))
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let bits = u32::from_be_bytes(self.read_array()?);
+        let bits = if bits >> 31 == 1 {
+            bits & !(1 << 31)
+        } else {
+            !bits
+        };
+        visitor.visit_f32(f32::from_bits(bits))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let bits = u64::from_be_bytes(self.read_array()?);
+        let bits = if bits >> 63 == 1 {
+            bits & !(1 << 63)
+        } else {
+            !bits
+        };
+        visitor.visit_f64(f64::from_bits(bits))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let ch = char::from_u32(u32::from_be_bytes(self.read_array()?))
+            .ok_or(Error::InvalidData("invalid char"))?;
+        visitor.visit_char(ch)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.read_escaped()? {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_str(
+                std::str::from_utf8(bytes).map_err(|_| Error::InvalidData("invalid utf-8"))?,
+            ),
+            Cow::Owned(bytes) => visitor.visit_string(
+                String::from_utf8(bytes).map_err(|_| Error::InvalidData("invalid utf-8"))?,
+            ),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.read_escaped()? {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.read_byte()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            _ => Err(Error::InvalidData("invalid option")),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Delimited { de: self })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Fixed {
+            de: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(Delimited { de: self })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error::NotSelfDescribing)
+    }
+}
+
+/// Accesses the elements of a sequence or map, which are each preceded by
+/// `ELEMENT` and terminated by `END`.
+struct Delimited<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> SeqAccess<'de> for Delimited<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.de.read_marker()? {
+            seed.deserialize(&mut *self.de).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for Delimited<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.de.read_marker()? {
+            seed.deserialize(&mut *self.de).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+/// Accesses the fields of a tuple, struct, or variant, which are written one
+/// after another.
+struct Fixed<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for Fixed<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.remaining == 0 {
+            Ok(None)
+        } else {
+            self.remaining -= 1;
+            seed.deserialize(&mut *self.de).map(Some)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let index = u32::from_be_bytes(self.read_array()?);
+        let variant = seed.deserialize(IntoDeserializer::<'_, Error>::into_deserializer(index))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        serde::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        serde::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
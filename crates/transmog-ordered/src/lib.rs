@@ -0,0 +1,335 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+mod de;
+mod ser;
+
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+pub use transmog;
+use transmog::{BorrowedDeserializer, Format, OwnedDeserializer};
+
+use self::{de::Deserializer, ser::Serializer};
+
+/// Precedes each element of a sequence or map.
+const ELEMENT: u8 = 1;
+/// Terminates a sequence or map. Sorts before [`ELEMENT`] so that shorter
+/// collections sort before longer collections they are a prefix of.
+const END: u8 = 0;
+
+/// Order-preserving implementor of [`Format`].
+///
+/// Values serialized with the same `Ordered` configuration compare byte-wise in
+/// the same order as the values themselves compare, making the output suitable
+/// for use as keys in ordered key-value stores. For types deriving
+/// [`Ord`], the ordering matches the derived ordering. Floating point numbers
+/// are ordered using [`f64::total_cmp`].
+///
+/// The encoding is not self-describing: values must be deserialized as the
+/// type they were serialized from.
+///
+/// In ascending order, strings and byte strings that don't contain a zero
+/// byte are stored verbatim, and are borrowed when deserializing using
+/// [`BorrowedDeserializer`]. Other strings must be unescaped, so `Cow` should
+/// be used to deserialize them rather than `&str`.
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct Ordered {
+    descending: bool,
+}
+
+impl Ordered {
+    /// Returns an instance whose output sorts in the reverse order of the
+    /// serialized values.
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    fn deserialize<'de, T: Deserialize<'de>>(self, data: &'de [u8]) -> Result<T, Error> {
+        let mut deserializer = Deserializer::new(data, self.descending);
+        let value = T::deserialize(&mut deserializer)?;
+        if deserializer.is_empty() {
+            Ok(value)
+        } else {
+            Err(Error::TrailingBytes)
+        }
+    }
+}
+
+impl<T> Format<'_, T> for Ordered
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        let mut bytes = Vec::new();
+        self.serialize_into(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, writer: W) -> Result<(), Self::Error> {
+        value.serialize(&mut Serializer::new(writer, self.descending))
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for Ordered
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        self.deserialize(data)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Ordered
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        self.deserialize(data)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        self.deserialize(&data)
+    }
+}
+
+/// Errors from [`Ordered`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error was reported by a value's `Serialize` or `Deserialize`
+    /// implementation.
+    #[error("{0}")]
+    Message(String),
+    /// The data ended before a complete value was read.
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+    /// The data is not a valid encoding of the requested type.
+    #[error("invalid data: {0}")]
+    InvalidData(&'static str),
+    /// Bytes remained after a complete value was read.
+    #[error("trailing bytes after value")]
+    TrailingBytes,
+    /// The type being deserialized requires a self-describing format, such
+    /// as untagged enums or `#[serde(flatten)]`.
+    #[error("the ordered encoding is not self-describing")]
+    NotSelfDescribing,
+    /// An Io error occurred outside of `Ordered`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Ordered::default());
+    transmog::test_util::test_format(&Ordered::default().descending());
+}
+
+#[test]
+fn invalid_data() {
+    let ordered = Ordered::default();
+    assert!(matches!(
+        OwnedDeserializer::<u32>::deserialize_owned(&ordered, &[0, 0]),
+        Err(Error::UnexpectedEof)
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<u8>::deserialize_owned(&ordered, &[0, 0]),
+        Err(Error::TrailingBytes)
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<String>::deserialize_owned(&ordered, &[b'a', 0, 1]),
+        Err(Error::InvalidData(_))
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<Vec<u8>>::deserialize_owned(&ordered, &[2]),
+        Err(Error::InvalidData(_))
+    ));
+}
+
+#[test]
+fn borrowed_strings() {
+    use std::borrow::Cow;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Entry<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        label: Cow<'a, str>,
+    }
+
+    let ordered = Ordered::default();
+    let encoded = ordered
+        .serialize(&Entry {
+            name: "name",
+            label: Cow::Borrowed("label"),
+        })
+        .unwrap();
+    let entry: Entry<'_> = ordered.deserialize_borrowed(&encoded).unwrap();
+    assert_eq!(entry.name, "name");
+    assert!(matches!(entry.label, Cow::Borrowed("label")));
+
+    // Escaped and descending strings can't be borrowed.
+    let encoded = ordered.serialize(&("a\0b", "a\0b")).unwrap();
+    let (name, label): (Cow<'_, str>, Cow<'_, str>) =
+        ordered.deserialize_borrowed(&encoded).unwrap();
+    assert!(matches!(name, Cow::Owned(name) if name == "a\0b"));
+    assert_eq!(label, "a\0b");
+    assert!(matches!(
+        BorrowedDeserializer::<&str>::deserialize_borrowed(&ordered, &encoded),
+        Err(Error::Message(_))
+    ));
+    let descending = Ordered::default().descending();
+    let encoded = descending.serialize(&"name").unwrap();
+    assert!(matches!(
+        BorrowedDeserializer::<&str>::deserialize_borrowed(&descending, &encoded),
+        Err(Error::Message(_))
+    ));
+    let name: String = descending.deserialize_owned(&encoded).unwrap();
+    assert_eq!(name, "name");
+    let long = "descending".repeat(20);
+    let encoded = descending.serialize(&long).unwrap();
+    assert_eq!(
+        &encoded[..long.len()],
+        &long.bytes().map(|byte| !byte).collect::<Vec<_>>()[..]
+    );
+    let name: String = descending.deserialize_owned(&encoded).unwrap();
+    assert_eq!(name, long);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cmp::Ordering, collections::BTreeMap, fmt::Debug};
+
+    use proptest::prelude::*;
+    use serde::{Deserialize, Serialize};
+    use transmog::{Format, OwnedDeserializer};
+
+    use crate::Ordered;
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    enum Event {
+        Created,
+        Renamed(String),
+        Moved { from: u32, to: u32 },
+    }
+
+    fn event() -> impl Strategy<Value = Event> {
+        prop_oneof![
+            Just(Event::Created),
+            any::<String>().prop_map(Event::Renamed),
+            (any::<u32>(), any::<u32>()).prop_map(|(from, to)| Event::Moved { from, to }),
+        ]
+    }
+
+    /// Asserts that `a` and `b` round trip and that their encodings compare
+    /// the same as `expected` in ascending order, and the opposite in
+    /// descending order.
+    fn check<T>(a: &T, b: &T, expected: Ordering) -> Result<(), TestCaseError>
+    where
+        T: Serialize + for<'de> Deserialize<'de> + Debug,
+    {
+        for (ordered, expected) in [
+            (Ordered::default(), expected),
+            (Ordered::default().descending(), expected.reverse()),
+        ] {
+            let encoded_a = ordered.serialize(a).unwrap();
+            let encoded_b = ordered.serialize(b).unwrap();
+            prop_assert_eq!(encoded_a.cmp(&encoded_b), expected);
+
+            let decoded: T = ordered.deserialize_owned(&encoded_a).unwrap();
+            prop_assert_eq!(format!("{decoded:?}"), format!("{a:?}"));
+        }
+        Ok(())
+    }
+
+    fn check_ord<T>(a: &T, b: &T) -> Result<(), TestCaseError>
+    where
+        T: Serialize + for<'de> Deserialize<'de> + Debug + Ord,
+    {
+        check(a, b, a.cmp(b))
+    }
+
+    proptest! {
+        #[test]
+        fn unsigned(a: u64, b: u64, c: u8, d: u8, e: u128, f: u128) {
+            check_ord(&a, &b)?;
+            check_ord(&c, &d)?;
+            check_ord(&e, &f)?;
+        }
+
+        #[test]
+        fn signed(a: i64, b: i64, c: i16, d: i16, e: i128, f: i128) {
+            check_ord(&a, &b)?;
+            check_ord(&c, &d)?;
+            check_ord(&e, &f)?;
+        }
+
+        #[test]
+        fn floats(
+            a in proptest::num::f64::ANY,
+            b in proptest::num::f64::ANY,
+            c in proptest::num::f32::ANY,
+            d in proptest::num::f32::ANY,
+        ) {
+            check(&a, &b, a.total_cmp(&b))?;
+            check(&c, &d, c.total_cmp(&d))?;
+        }
+
+        #[test]
+        fn strings(a: String, b: String) {
+            check_ord(&a, &b)?;
+            // Strings that share a prefix, including embedded nulls.
+            check_ord(&format!("{a}\0{b}"), &a)?;
+            check_ord(&format!("{a}\0"), &format!("{a}\0\0"))?;
+        }
+
+        #[test]
+        fn tuples(a: (bool, i32, String, Option<char>), b: (bool, i32, String, Option<char>)) {
+            check_ord(&a, &b)?;
+        }
+
+        #[test]
+        fn collections(a: Vec<Option<u16>>, b: Vec<Option<u16>>, c: BTreeMap<String, i8>, d: BTreeMap<String, i8>) {
+            check_ord(&a, &b)?;
+            check_ord(&c, &d)?;
+        }
+
+        #[test]
+        fn enums(a in event(), b in event()) {
+            check_ord(&a, &b)?;
+            check_ord(&(a.clone(), 1_u8), &(a, 0_u8))?;
+        }
+    }
+}
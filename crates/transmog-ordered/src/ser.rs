@@ -0,0 +1,350 @@
+use std::io::Write;
+
+use serde::{
+    ser::{
+        SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize,
+};
+
+use crate::{Error, ELEMENT, END};
+
+/// Writes values using an encoding whose byte-wise ordering matches the
+/// ordering of the values.
+pub struct Serializer<W> {
+    writer: W,
+    descending: bool,
+}
+
+impl<W: Write> Serializer<W> {
+    pub fn new(writer: W, descending: bool) -> Self {
+        Self { writer, descending }
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if self.descending {
+            // Inverting every byte of a prefix-free encoding reverses its
+            // ordering.
+            let mut inverted = [0; 64];
+            for chunk in bytes.chunks(inverted.len()) {
+                for (inverted, byte) in inverted.iter_mut().zip(chunk) {
+                    *inverted = !byte;
+                }
+                self.writer.write_all(&inverted[..chunk.len()])?;
+            }
+        } else {
+            self.writer.write_all(bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `bytes` such that no encoded value is a prefix of another:
+    /// each zero byte is escaped as `[0, 0xFF]`, and the value is terminated
+    /// by `[0, 0]`.
+    fn write_escaped(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let mut chunks = bytes.split(|byte| *byte == 0);
+        if let Some(chunk) = chunks.next() {
+            self.write(chunk)?;
+        }
+        for chunk in chunks {
+            self.write(&[0, 0xFF])?;
+            self.write(chunk)?;
+        }
+        self.write(&[0, 0])
+    }
+}
+
+impl<W: Write> serde::Serializer for &mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.write(&[u8::from(v)])
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.write(&(v ^ i8::MIN).to_be_bytes())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.write(&(v ^ i16::MIN).to_be_bytes())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.write(&(v ^ i32::MIN).to_be_bytes())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.write(&(v ^ i64::MIN).to_be_bytes())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.write(&(v ^ i128::MIN).to_be_bytes())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.write(&[v])
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.write(&v.to_be_bytes())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.write(&v.to_be_bytes())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.write(&v.to_be_bytes())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.write(&v.to_be_bytes())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        let bits = v.to_bits();
+        // Negative values have all of their bits inverted so that larger
+        // magnitudes sort first, while positive values only have their sign
+        // bit set so that they sort after all negative values.
+        let bits = if bits >> 31 == 1 {
+            !bits
+        } else {
+            bits | 1 << 31
+        };
+        self.write(&bits.to_be_bytes())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        let bits = v.to_bits();
+        let bits = if bits >> 63 == 1 {
+            !bits
+        } else {
+            bits | 1 << 63
+        };
+        self.write(&bits.to_be_bytes())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.serialize_u32(u32::from(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.write_escaped(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.write_escaped(v)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.write(&[0])
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Error> {
+        self.write(&[1])?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.serialize_u32(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Error> {
+        self.serialize_u32(variant_index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Error> {
+        self.serialize_u32(variant_index)?;
+        Ok(self)
+    }
+}
+
+// Sequences and maps have a variable number of elements. Each element is
+// preceded by `ELEMENT`, and the collection is terminated by `END`, which sorts
+// shorter collections before longer collections that they are a prefix of.
+
+impl<W: Write> SerializeSeq for &mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.write(&[ELEMENT])?;
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.write(&[END])
+    }
+}
+
+impl<W: Write> SerializeMap for &mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.write(&[ELEMENT])?;
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.write(&[END])
+    }
+}
+
+// Tuples, structs, and variants have a fixed number of fields, so their fields
+// are written one after another.
+
+impl<W: Write> SerializeTuple for &mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> SerializeTupleStruct for &mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> SerializeTupleVariant for &mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> SerializeStruct for &mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> SerializeStructVariant for &mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::io::{Read, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+pub use serde_pickle;
+use serde_pickle::{DeOptions, SerOptions};
+pub use transmog;
+use transmog::{Format, OwnedDeserializer};
+
+/// Pickle implementor of [`Format`].
+///
+/// By default, values are serialized using pickle protocol 3, and
+/// deserialization fails on pickles referencing classes or functions
+/// ("globals") other than the few builtins that `serde-pickle` understands.
+/// `serde-pickle` never imports or runs the globals a pickle references, so no
+/// Python code is ever executed.
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct Pickle {
+    protocol: Protocol,
+    replace_unresolved_globals: bool,
+}
+
+/// A pickle protocol version that [`Pickle`] can serialize with.
+///
+/// Deserialization accepts every protocol version regardless of this setting.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Protocol {
+    /// Protocol 2, readable by Python 2.3 and later.
+    V2,
+    /// Protocol 3, readable by Python 3.0 and later.
+    #[default]
+    V3,
+}
+
+impl Pickle {
+    /// Returns an instance that serializes using `protocol`.
+    pub fn protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Returns an instance that replaces references to classes or functions
+    /// that `serde-pickle` cannot resolve with `None`, instead of failing to
+    /// deserialize them.
+    ///
+    /// This only changes how unresolved globals are represented. They are
+    /// still never imported or called.
+    pub fn replace_unresolved_globals(mut self) -> Self {
+        self.replace_unresolved_globals = true;
+        self
+    }
+
+    fn ser_options(self) -> SerOptions {
+        match self.protocol {
+            Protocol::V2 => SerOptions::new().proto_v2(),
+            Protocol::V3 => SerOptions::new(),
+        }
+    }
+
+    fn de_options(self) -> DeOptions {
+        if self.replace_unresolved_globals {
+            DeOptions::new().replace_unresolved_globals()
+        } else {
+            DeOptions::new()
+        }
+    }
+}
+
+impl<T> Format<'_, T> for Pickle
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_pickle::to_vec(value, self.ser_options()).map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        serde_pickle::to_writer(&mut writer, value, self.ser_options()).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Pickle
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        serde_pickle::from_slice(data, self.de_options()).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, reader: R) -> Result<T, Self::Error> {
+        serde_pickle::from_reader(reader, self.de_options()).map_err(Error::from)
+    }
+}
+
+/// Errors from [`Pickle`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred from serializing or deserializing.
+    #[error("pickle error: {0}")]
+    Pickle(#[from] serde_pickle::Error),
+    /// An Io error occurred outside of `Pickle`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Pickle::default());
+    transmog::test_util::test_format(&Pickle::default().protocol(Protocol::V2));
+    transmog::test_util::test_format(&Pickle::default().replace_unresolved_globals());
+}
+
+#[test]
+fn unresolved_globals() {
+    // The pickle of `[Foo]`, where `Foo` is a class defined in `__main__`:
+    // PROTO 2, EMPTY_LIST, GLOBAL '__main__ Foo', APPEND, STOP.
+    let pickle = b"\x80\x02]c__main__\nFoo\na.";
+
+    assert!(matches!(
+        OwnedDeserializer::<Vec<Option<String>>>::deserialize_owned(&Pickle::default(), pickle),
+        Err(Error::Pickle(_))
+    ));
+
+    let replaced: Vec<Option<String>> = Pickle::default()
+        .replace_unresolved_globals()
+        .deserialize_owned(pickle)
+        .unwrap();
+    assert_eq!(replaced, vec![None]);
+}
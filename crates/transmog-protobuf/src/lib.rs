@@ -0,0 +1,173 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::io::{ErrorKind, Read, Write};
+
+pub use prost;
+use prost::Message;
+pub use transmog;
+use transmog::{Format, OwnedDeserializer};
+
+/// Protocol Buffers implementor of [`Format`].
+#[derive(Clone, Default)]
+#[must_use]
+pub struct Protobuf {
+    length_delimited: bool,
+}
+
+impl Protobuf {
+    /// Returns an instance configured to prefix each message with its length
+    /// encoded as a varint. See [`Message::encode_length_delimited`] for more
+    /// information.
+    ///
+    /// When deserializing from a reader, only the bytes of a single message
+    /// are consumed, allowing multiple messages to be read from one stream.
+    pub fn length_delimited(mut self) -> Self {
+        self.length_delimited = true;
+        self
+    }
+}
+
+impl<T> Format<'_, T> for Protobuf
+where
+    T: Message,
+{
+    type Error = Error;
+
+    fn serialized_size(&self, value: &T) -> Result<Option<usize>, Self::Error> {
+        let length = value.encoded_len();
+        if self.length_delimited {
+            Ok(Some(prost::length_delimiter_len(length) + length))
+        } else {
+            Ok(Some(length))
+        }
+    }
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        if self.length_delimited {
+            Ok(value.encode_length_delimited_to_vec())
+        } else {
+            Ok(value.encode_to_vec())
+        }
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        let bytes = self.serialize(value)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Protobuf
+where
+    T: Message + Default,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        if self.length_delimited {
+            T::decode_length_delimited(data).map_err(Error::from)
+        } else {
+            T::decode(data).map_err(Error::from)
+        }
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut bytes = Vec::new();
+        if self.length_delimited {
+            let length = read_length_delimiter(&mut reader)?;
+            let expected =
+                u64::try_from(length).map_err(|_| std::io::Error::from(ErrorKind::OutOfMemory))?;
+            reader.take(expected).read_to_end(&mut bytes)?;
+            if bytes.len() < length {
+                return Err(Error::from(std::io::Error::from(ErrorKind::UnexpectedEof)));
+            }
+        } else {
+            reader.read_to_end(&mut bytes)?;
+        }
+        T::decode(&bytes[..]).map_err(Error::from)
+    }
+}
+
+fn read_length_delimiter<R: Read>(mut reader: R) -> Result<usize, Error> {
+    // A varint-encoded length is at most 10 bytes long.
+    let mut buffer = [0_u8; 10];
+    for index in 0..buffer.len() {
+        reader.read_exact(&mut buffer[index..=index])?;
+        if buffer[index] & 0x80 == 0 {
+            return prost::decode_length_delimiter(&buffer[..=index]).map_err(Error::from);
+        }
+    }
+    prost::decode_length_delimiter(&buffer[..]).map_err(Error::from)
+}
+
+/// Errors from [`Protobuf`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred while decoding a message.
+    #[error("decode error: {0}")]
+    Decode(#[from] prost::DecodeError),
+    /// An Io error occurred outside of encoding or decoding.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Protobuf::default());
+    transmog::test_util::test_format(&Protobuf::default().length_delimited());
+}
+
+#[test]
+fn length_delimited_stream() {
+    #[derive(Clone, PartialEq, Message)]
+    struct Test {
+        #[prost(string, tag = "1")]
+        name: String,
+        #[prost(uint64, repeated, tag = "2")]
+        values: Vec<u64>,
+    }
+
+    let protobuf = Protobuf::default().length_delimited();
+    let first = Test {
+        name: String::from("first"),
+        values: vec![1, 2, 3],
+    };
+    let second = Test {
+        name: String::from("second"),
+        values: Vec::new(),
+    };
+    let mut stream = Vec::new();
+    protobuf.serialize_into(&first, &mut stream).unwrap();
+    protobuf.serialize_into(&second, &mut stream).unwrap();
+    assert_eq!(
+        stream.len(),
+        protobuf.serialized_size(&first).unwrap().unwrap()
+            + protobuf.serialized_size(&second).unwrap().unwrap()
+    );
+
+    let mut reader = &stream[..];
+    let deserialized: Test = protobuf.deserialize_from(&mut reader).unwrap();
+    assert_eq!(deserialized, first);
+    let deserialized: Test = protobuf.deserialize_from(&mut reader).unwrap();
+    assert_eq!(deserialized, second);
+    assert!(reader.is_empty());
+
+    // A truncated message is reported rather than decoded.
+    let truncated = &stream[..stream.len() - 1];
+    let mut reader = &truncated[protobuf.serialized_size(&first).unwrap().unwrap()..];
+    assert!(matches!(
+        OwnedDeserializer::<Test>::deserialize_from(&protobuf, &mut reader),
+        Err(Error::Io(_))
+    ));
+}
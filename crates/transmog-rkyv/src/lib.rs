@@ -0,0 +1,151 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::io::{Read, Write};
+
+pub use rkyv;
+use rkyv::{
+    api::high::{HighDeserializer, HighSerializer, HighValidator},
+    bytecheck::CheckBytes,
+    rancor,
+    ser::allocator::ArenaHandle,
+    util::AlignedVec,
+    Archive, Deserialize, Serialize,
+};
+pub use transmog;
+use transmog::{ArchivedDeserializer, Format, OwnedDeserializer};
+
+/// The serializer used by [`Rkyv`].
+pub type RkyvSerializer<'a> = HighSerializer<AlignedVec, ArenaHandle<'a>, rancor::Error>;
+
+/// The deserializer used by [`Rkyv`].
+pub type RkyvDeserializer = HighDeserializer<rancor::Error>;
+
+/// The validator used by [`Rkyv`] to check archived data before accessing it.
+pub type RkyvValidator<'a> = HighValidator<'a, rancor::Error>;
+
+/// Rkyv implementor of [`Format`].
+///
+/// All data is validated before it is accessed or deserialized.
+#[derive(Clone, Default)]
+#[must_use]
+pub struct Rkyv;
+
+impl Rkyv {
+    /// Serializes `value` into an [`AlignedVec`]. The returned buffer can be
+    /// passed directly to [`ArchivedDeserializer::deserialize_archived`].
+    pub fn serialize_aligned<T>(&self, value: &T) -> Result<AlignedVec, Error>
+    where
+        T: for<'a> Serialize<RkyvSerializer<'a>>,
+    {
+        rkyv::to_bytes(value).map_err(Error::from)
+    }
+}
+
+impl<T> Format<'_, T> for Rkyv
+where
+    T: for<'a> Serialize<RkyvSerializer<'a>>,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        self.serialize_aligned(value).map(AlignedVec::into_vec)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        let bytes = self.serialize_aligned(value)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+impl<'a, T> ArchivedDeserializer<'a, T> for Rkyv
+where
+    T: Archive + for<'b> Serialize<RkyvSerializer<'b>>,
+    T::Archived: for<'b> CheckBytes<RkyvValidator<'b>> + 'a,
+{
+    type Archived = T::Archived;
+
+    /// Validates `data` and returns a reference to the archived `T` it
+    /// contains. `data` must be aligned to the archived type's alignment.
+    fn deserialize_archived(&self, data: &'a [u8]) -> Result<&'a Self::Archived, Self::Error> {
+        rkyv::access::<T::Archived, rancor::Error>(data).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Rkyv
+where
+    T: Archive + for<'a> Serialize<RkyvSerializer<'a>>,
+    T::Archived: for<'a> CheckBytes<RkyvValidator<'a>> + Deserialize<T, RkyvDeserializer>,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        // `data` has no alignment guarantees, so it is copied into an aligned
+        // buffer before being validated.
+        let mut aligned = AlignedVec::<16>::with_capacity(data.len());
+        aligned.extend_from_slice(data);
+        rkyv::from_bytes::<T, rancor::Error>(&aligned).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut aligned = AlignedVec::<16>::new();
+        aligned.extend_from_reader(&mut reader)?;
+        rkyv::from_bytes::<T, rancor::Error>(&aligned).map_err(Error::from)
+    }
+}
+
+/// Errors from [`Rkyv`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred from serializing, validating, or deserializing.
+    #[error("rkyv error: {0}")]
+    Rkyv(#[from] rancor::Error),
+    /// An Io error occurred outside of `Rkyv`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Rkyv);
+}
+
+#[test]
+fn archived_access() {
+    let rkyv = Rkyv;
+    let value = vec![String::from("hello"), String::from("world")];
+    let serialized = rkyv.serialize_aligned(&value).unwrap();
+    let archived =
+        <Rkyv as ArchivedDeserializer<'_, Vec<String>>>::deserialize_archived(&rkyv, &serialized)
+            .unwrap();
+    assert_eq!(archived.len(), 2);
+    assert_eq!(archived[0], "hello");
+    assert_eq!(archived[1], "world");
+}
+
+#[test]
+fn invalid_data() {
+    let rkyv = Rkyv;
+    let serialized = rkyv.serialize_aligned(&String::from("hello")).unwrap();
+    // Truncating the buffer invalidates the relative pointers of the archive.
+    let truncated = &serialized[serialized.len() - 4..];
+    assert!(matches!(
+        <Rkyv as ArchivedDeserializer<'_, String>>::deserialize_archived(&rkyv, truncated),
+        Err(Error::Rkyv(_))
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<String>::deserialize_owned(&rkyv, truncated),
+        Err(Error::Rkyv(_))
+    ));
+}
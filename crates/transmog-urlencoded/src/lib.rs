@@ -0,0 +1,260 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::io::{Read, Write};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+pub use serde_qs;
+pub use serde_urlencoded;
+pub use transmog;
+use transmog::{BorrowedDeserializer, Format, OwnedDeserializer};
+
+/// `application/x-www-form-urlencoded` implementor of [`Format`], powered by
+/// `serde_urlencoded`.
+///
+/// Only flat structures can be represented: the serialized value must be a
+/// struct, map, or sequence of pairs whose values are primitives. Use
+/// [`NestedUrlEncoded`] for nested structures.
+#[derive(Clone, Copy, Debug, Default)]
+#[must_use]
+pub struct UrlEncoded;
+
+impl<T> Format<'_, T> for UrlEncoded
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        serde_urlencoded::to_string(value)
+            .map(String::into_bytes)
+            .map_err(Error::from)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        writer.write_all(serde_urlencoded::to_string(value)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for UrlEncoded
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        serde_urlencoded::from_bytes(data).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for UrlEncoded
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        serde_urlencoded::from_bytes(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        self.deserialize_owned(&data)
+    }
+}
+
+/// Query string implementor of [`Format`] supporting nested structures,
+/// powered by `serde_qs`.
+///
+/// Nested fields are encoded using brackets, e.g. `user[name]=Alice` or
+/// `ids[0]=1&ids[1]=2`.
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct NestedUrlEncoded {
+    max_depth: usize,
+    strict: bool,
+}
+
+impl Default for NestedUrlEncoded {
+    /// Returns an instance with a maximum depth of 5 that operates in strict
+    /// mode.
+    fn default() -> Self {
+        Self {
+            max_depth: 5,
+            strict: true,
+        }
+    }
+}
+
+impl NestedUrlEncoded {
+    /// Returns an instance that deserializes keys nested at most `max_depth`
+    /// levels deep. Brackets beyond `max_depth` are treated as part of the
+    /// key.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns an instance that accepts percent-encoded brackets in keys when
+    /// deserializing. This is useful when decoding query strings produced by
+    /// clients that encode brackets, but can misinterpret values that contain
+    /// encoded brackets.
+    pub fn lenient(mut self) -> Self {
+        self.strict = false;
+        self
+    }
+
+    fn config(self) -> serde_qs::Config {
+        serde_qs::Config::new(self.max_depth, self.strict)
+    }
+}
+
+impl<T> Format<'_, T> for NestedUrlEncoded
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        let mut data = Vec::new();
+        serde_qs::to_writer(value, &mut data)?;
+        Ok(data)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        serde_qs::to_writer(value, &mut writer).map_err(Error::from)
+    }
+}
+
+impl<'a, T> BorrowedDeserializer<'a, T> for NestedUrlEncoded
+where
+    T: Serialize + Deserialize<'a>,
+{
+    fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error> {
+        self.config().deserialize_bytes(data).map_err(Error::from)
+    }
+}
+
+impl<T> OwnedDeserializer<T> for NestedUrlEncoded
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        self.config().deserialize_bytes(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, mut reader: R) -> Result<T, Self::Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        self.deserialize_owned(&data)
+    }
+}
+
+/// Errors from [`UrlEncoded`] and [`NestedUrlEncoded`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred while serializing with [`UrlEncoded`].
+    #[error("serialization error: {0}")]
+    Serialize(#[from] serde_urlencoded::ser::Error),
+    /// An error occurred while deserializing with [`UrlEncoded`].
+    #[error("deserialization error: {0}")]
+    Deserialize(#[from] serde_urlencoded::de::Error),
+    /// An error occurred while serializing or deserializing with
+    /// [`NestedUrlEncoded`].
+    #[error("query string error: {0}")]
+    Nested(#[from] serde_qs::Error),
+    /// An Io error occurred outside of encoding or decoding.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn flat() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Login<'a> {
+        user: &'a str,
+        remember: bool,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct OwnedLogin {
+        user: String,
+        remember: bool,
+    }
+
+    let login = Login {
+        user: "alice",
+        remember: true,
+    };
+    let urlencoded = UrlEncoded;
+    let serialized = urlencoded.serialize(&login).unwrap();
+    assert_eq!(serialized, b"user=alice&remember=true");
+    let deserialized: Login<'_> = urlencoded.deserialize_borrowed(&serialized).unwrap();
+    assert_eq!(deserialized, login);
+
+    let deserialized: OwnedLogin = urlencoded
+        .deserialize_owned(b"user=bob+smith%21&remember=false")
+        .unwrap();
+    assert_eq!(deserialized.user, "bob smith!");
+    assert!(!deserialized.remember);
+
+    assert!(matches!(
+        Format::<u64>::serialize(&urlencoded, &1),
+        Err(Error::Serialize(_))
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<OwnedLogin>::deserialize_from(&urlencoded, &b"remember=yes"[..]),
+        Err(Error::Deserialize(_))
+    ));
+}
+
+#[test]
+fn nested() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Search {
+        query: String,
+        filter: Filter,
+        ids: Vec<u32>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Filter {
+        author: String,
+        year: u16,
+    }
+
+    let search = Search {
+        query: String::from("transmog"),
+        filter: Filter {
+            author: String::from("alice"),
+            year: 2022,
+        },
+        ids: vec![1, 2],
+    };
+    let nested = NestedUrlEncoded::default();
+    let serialized = nested.serialize(&search).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&serialized).unwrap(),
+        "query=transmog&filter[author]=alice&filter[year]=2022&ids[0]=1&ids[1]=2"
+    );
+    let deserialized: Search = nested.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, search);
+
+    let encoded = b"query=transmog&filter%5Bauthor%5D=alice&filter%5Byear%5D=2022&ids%5B0%5D=1";
+    assert!(matches!(
+        OwnedDeserializer::<Search>::deserialize_from(&nested, &encoded[..]),
+        Err(Error::Nested(_))
+    ));
+    let deserialized: Search = nested.lenient().deserialize_from(&encoded[..]).unwrap();
+    assert_eq!(deserialized.filter, search.filter);
+}
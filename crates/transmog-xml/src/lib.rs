@@ -0,0 +1,185 @@
+#![doc = include_str!("./.crate-docs.md")]
+#![forbid(unsafe_code)]
+#![warn(
+    clippy::cargo,
+    missing_docs,
+    // clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    future_incompatible,
+    rust_2018_idioms,
+)]
+#![allow(
+    clippy::missing_errors_doc, // TODO clippy::missing_errors_doc
+    clippy::option_if_let_else,
+)]
+
+use std::io::{BufReader, Read, Write};
+
+pub use quick_xml;
+use quick_xml::se::Serializer;
+use serde::{de::DeserializeOwned, Serialize};
+pub use transmog;
+use transmog::{Format, OwnedDeserializer};
+
+/// The XML declaration written by [`Xml::declaration`].
+pub const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+/// XML implementor of [`Format`].
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct Xml {
+    root_element: Option<String>,
+    indent: Option<(char, usize)>,
+    declaration: bool,
+}
+
+impl Xml {
+    /// Returns an instance configured to wrap serialized values in an element
+    /// named `name`.
+    ///
+    /// Without a root element, structs and enums are wrapped in an element
+    /// named after their type, and primitives and sequences cannot be
+    /// serialized.
+    pub fn root_element(mut self, name: impl Into<String>) -> Self {
+        self.root_element = Some(name.into());
+        self
+    }
+
+    /// Returns an instance configured to write each nested element on its own
+    /// line, indented by `size` repetitions of `character` per level.
+    pub fn indent(mut self, character: char, size: usize) -> Self {
+        self.indent = Some((character, size));
+        self
+    }
+
+    /// Returns an instance configured to begin each serialized document with
+    /// [`XML_DECLARATION`].
+    pub fn declaration(mut self) -> Self {
+        self.declaration = true;
+        self
+    }
+
+    fn serialize_to_string<T: Serialize>(&self, value: &T) -> Result<String, Error> {
+        let mut xml = String::new();
+        if self.declaration {
+            xml.push_str(XML_DECLARATION);
+            if self.indent.is_some() {
+                xml.push('\n');
+            }
+        }
+
+        let mut serializer = Serializer::with_root(&mut xml, self.root_element.as_deref())?;
+        if let Some((character, size)) = self.indent {
+            serializer.indent(character, size);
+        }
+        value.serialize(serializer)?;
+
+        Ok(xml)
+    }
+}
+
+impl<T> Format<'_, T> for Xml
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn serialize(&self, value: &T) -> Result<Vec<u8>, Self::Error> {
+        self.serialize_to_string(value).map(String::into_bytes)
+    }
+
+    fn serialize_into<W: Write>(&self, value: &T, mut writer: W) -> Result<(), Self::Error> {
+        let xml = self.serialize_to_string(value)?;
+        writer.write_all(xml.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl<T> OwnedDeserializer<T> for Xml
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn deserialize_owned(&self, data: &[u8]) -> Result<T, Self::Error> {
+        quick_xml::de::from_reader(data).map_err(Error::from)
+    }
+
+    fn deserialize_from<R: Read>(&self, reader: R) -> Result<T, Self::Error> {
+        quick_xml::de::from_reader(BufReader::new(reader)).map_err(Error::from)
+    }
+}
+
+/// Errors from [`Xml`].
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// An error occurred while serializing a value.
+    #[error("serialization error: {0}")]
+    Serialize(#[from] quick_xml::SeError),
+    /// An error occurred while deserializing a value.
+    #[error("deserialization error: {0}")]
+    Deserialize(#[from] quick_xml::DeError),
+    /// An Io error occurred outside of `Xml`.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn format_tests() {
+    transmog::test_util::test_format(&Xml::default().root_element("value"));
+    transmog::test_util::test_format(
+        &Xml::default()
+            .root_element("value")
+            .indent(' ', 2)
+            .declaration(),
+    );
+}
+
+#[test]
+fn documents() {
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Order {
+        id: u64,
+        item: Vec<String>,
+    }
+
+    let order = Order {
+        id: 1,
+        item: vec![String::from("widget"), String::from("gadget")],
+    };
+
+    let xml = Xml::default();
+    let serialized = xml.serialize(&order).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&serialized).unwrap(),
+        "<Order><id>1</id><item>widget</item><item>gadget</item></Order>"
+    );
+
+    let xml = Xml::default()
+        .root_element("order")
+        .indent(' ', 2)
+        .declaration();
+    let serialized = xml.serialize(&order).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&serialized).unwrap(),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <order>\n  \
+           <id>1</id>\n  \
+           <item>widget</item>\n  \
+           <item>gadget</item>\n\
+         </order>"
+    );
+    let deserialized: Order = xml.deserialize_owned(&serialized).unwrap();
+    assert_eq!(deserialized, order);
+    let deserialized: Order = xml.deserialize_from(&serialized[..]).unwrap();
+    assert_eq!(deserialized, order);
+
+    assert!(matches!(
+        Format::<Order>::serialize(&Xml::default().root_element("not valid"), &order),
+        Err(Error::Serialize(_))
+    ));
+    assert!(matches!(
+        OwnedDeserializer::<Order>::deserialize_owned(&xml, b"<order><id>1</id>"),
+        Err(Error::Deserialize(_))
+    ));
+}
@@ -51,6 +51,17 @@ pub trait BorrowedDeserializer<'a, T>: Format<'a, T> {
     fn deserialize_borrowed(&self, data: &'a [u8]) -> Result<T, Self::Error>;
 }
 
+/// A deserializer that provides zero-copy access to the archived
+/// representation of `T` stored within serialized data.
+pub trait ArchivedDeserializer<'a, T>: Format<'a, T> {
+    /// The archived representation of `T`.
+    type Archived: ?Sized + 'a;
+
+    /// Returns a reference to the archived representation of `T` stored in
+    /// `data` without deserializing it.
+    fn deserialize_archived(&self, data: &'a [u8]) -> Result<&'a Self::Archived, Self::Error>;
+}
+
 /// A deserializer that does not attempt to borrow data when deserializing.
 pub trait OwnedDeserializer<T>: Format<'static, T> {
     /// Deserialize `T` from `data`.
@@ -23,6 +23,20 @@ impl khonsu_tools::publish::Config for Config {
             String::from("crates/transmog-cbor"),
             String::from("crates/transmog-json"),
             String::from("crates/transmog-pot"),
+            String::from("crates/transmog-rkyv"),
+            String::from("crates/transmog-protobuf"),
+            String::from("crates/transmog-borsh"),
+            String::from("crates/transmog-bincode2"),
+            String::from("crates/transmog-bitcode"),
+            String::from("crates/transmog-avro"),
+            String::from("crates/transmog-xml"),
+            String::from("crates/transmog-csv"),
+            String::from("crates/transmog-urlencoded"),
+            String::from("crates/transmog-flexbuffers"),
+            String::from("crates/transmog-ordered"),
+            String::from("crates/transmog-bare"),
+            String::from("crates/transmog-pickle"),
+            String::from("crates/transmog-json5"),
             String::from("crates/transmog-async"),
             String::from("crates/transmog-versions"),
         ]